@@ -0,0 +1,33 @@
+use std::{os::raw::c_int, sync::Mutex};
+
+use glfw::{ffi, JoystickEvent, JoystickId};
+
+/// User function that is called for joystick connections.
+pub type JoystickCallback = fn(JoystickId, JoystickEvent);
+
+static JOYSTICK_CALLBACK: Mutex<Option<JoystickCallback>> = Mutex::new(None);
+
+extern "C" fn joystick_callback(id: c_int, event: c_int) {
+    let callback = *JOYSTICK_CALLBACK.lock().unwrap();
+    let event = match event {
+        ffi::CONNECTED => JoystickEvent::Connected,
+        ffi::DISCONNECTED => JoystickEvent::Disconnected,
+        _ => return,
+    };
+    if let (Some(callback), Some(id)) = (callback, JoystickId::from_i32(id)) {
+        callback(id, event);
+    }
+}
+
+/// Sets the function that is called when a joystick or a gamepad is connected
+/// or disconnected, overwriting the previous one.
+/// The callback is global, thus, it is not tied to any [Display](crate::Display),
+/// and it is called from the event polling of any of them.
+/// Must be called on the main thread while GLFW is initialized; that is, while
+/// there is a display.
+pub fn set_joystick_callback(f: JoystickCallback) {
+    *JOYSTICK_CALLBACK.lock().unwrap() = Some(f);
+    unsafe {
+        ffi::glfwSetJoystickCallback(Some(joystick_callback));
+    }
+}
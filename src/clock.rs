@@ -0,0 +1,116 @@
+/// Pausable clock that reads seconds from a raw time source, like the GLFW
+/// timer, which keeps running while the clock is paused.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Clock {
+    start: f64,
+    paused_at: Option<f64>,
+    paused: f64,
+}
+
+impl Clock {
+    /// Starts the clock at the given raw time.
+    pub(crate) fn new(raw: f64) -> Self {
+        Self {
+            start: raw,
+            paused_at: None,
+            paused: 0.0,
+        }
+    }
+
+    /// Returns the raw time without the durations the clock was paused.
+    pub(crate) fn time(&self, raw: f64) -> f64 {
+        self.paused_at.unwrap_or(raw) - self.paused
+    }
+
+    /// Returns the time since the clock was started.
+    pub(crate) fn elapsed(&self, raw: f64) -> f64 {
+        self.time(raw) - self.start
+    }
+
+    /// Freezes the clock if it is running.
+    pub(crate) fn pause(&mut self, raw: f64) {
+        self.paused_at.get_or_insert(raw);
+    }
+
+    /// Continues the clock from where it was frozen.
+    pub(crate) fn resume(&mut self, raw: f64) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused += raw - paused_at;
+        }
+    }
+
+    /// Returns whether the clock is frozen.
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+}
+
+/// Durations of the last frames in a ring buffer, which does not allocate
+/// after it is sized.
+#[derive(Clone, Debug)]
+pub(crate) struct FrameTimes {
+    durations: Vec<f64>,
+    frames: usize,
+    next: usize,
+}
+
+impl FrameTimes {
+    /// Creates an empty ring buffer for the given amount of frames.
+    pub(crate) fn new(frames: usize) -> Self {
+        Self {
+            durations: Vec::with_capacity(frames),
+            frames,
+            next: 0,
+        }
+    }
+
+    /// Records the duration of a frame, replacing the oldest one if full.
+    pub(crate) fn push(&mut self, duration: f64) {
+        if self.durations.len() < self.frames {
+            self.durations.push(duration);
+        } else if let Some(oldest) = self.durations.get_mut(self.next) {
+            *oldest = duration;
+        }
+        self.next = (self.next + 1) % self.frames.max(1);
+    }
+
+    /// Returns the frame rate over the recorded frames, which is zero if
+    /// there are none.
+    pub(crate) fn average_fps(&self) -> f64 {
+        let total: f64 = self.durations.iter().sum();
+        if total > 0.0 {
+            self.durations.len() as f64 / total
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock, FrameTimes};
+
+    #[test]
+    fn oldest_frames_are_replaced() {
+        let mut frames = FrameTimes::new(2);
+        assert_eq!(frames.average_fps(), 0.0);
+        frames.push(1.0);
+        assert_eq!(frames.average_fps(), 1.0);
+        frames.push(0.5);
+        frames.push(0.5);
+        assert_eq!(frames.average_fps(), 2.0);
+    }
+
+    #[test]
+    fn paused_durations_are_skipped() {
+        let mut clock = Clock::new(10.0);
+        assert_eq!(clock.elapsed(12.0), 2.0);
+        clock.pause(12.0);
+        clock.pause(13.0);
+        assert!(clock.is_paused());
+        assert_eq!(clock.elapsed(15.0), 2.0);
+        clock.resume(20.0);
+        assert_eq!(clock.elapsed(21.0), 3.0);
+        assert_eq!(clock.time(21.0), 13.0);
+    }
+}
@@ -0,0 +1,133 @@
+use glfw::{Context, Glfw, Window, WindowEvent, WindowHint, WindowMode};
+use std::sync::mpsc::Receiver;
+
+use crate::{Display, DisplayError, Options};
+
+/// Displays whose contexts share the objects, like the textures, buffers and
+/// programs, with a hidden root context that the group owns.
+/// Since the root is not one of the displays, any of them can be closed
+/// without losing the shared objects, which live until the group is dropped.
+/// **Container objects, like the vertex arrays and the framebuffers, are not
+/// shared by OpenGL; they must be created for each display.**
+///
+/// Dropping the group closes the windows of the displays from the last created
+/// to the first, then destroys the root context. The displays that were [removed](Self::remove)
+/// before keep the objects alive on their own.
+pub struct ContextGroup<T: FnMut(WindowEvent)> {
+    displays: Vec<Display<T>>,
+    root: Window,
+    _root_events: Receiver<(f64, WindowEvent)>,
+}
+
+impl<T: FnMut(WindowEvent)> ContextGroup<T> {
+    /// Creates an empty group with the GLFW from [init](crate::init), whose
+    /// root context is created with the context options of the given ones,
+    /// like the `client_api` and the `debug_context`. The window options, like
+    /// the size and `fullscreen`, are ignored for the root.
+    ///
+    /// # Panics
+    ///
+    /// - Same as [init](crate::init).
+    pub fn new(opt: &Options) -> Result<Self, DisplayError> {
+        Self::with_glfw(&mut crate::init(), opt)
+    }
+
+    /// Creates an empty group with the given GLFW, like [new](Self::new).
+    pub fn with_glfw(glfw: &mut Glfw, opt: &Options) -> Result<Self, DisplayError> {
+        opt.config(glfw);
+        glfw.window_hint(WindowHint::Visible(false));
+        let (root, root_events) = glfw
+            .create_window(1, 1, &opt.title, WindowMode::Windowed)
+            .ok_or(DisplayError::WindowCreation)?;
+        Ok(Self {
+            displays: Vec::new(),
+            root,
+            _root_events: root_events,
+        })
+    }
+
+    /// Creates a display in the group, whose context shares the objects of the
+    /// root and becomes the current one. The context options must be
+    /// compatible with the root's; otherwise, the creation fails.
+    /// Returns its index, which changes when the displays before it are
+    /// [removed](Self::remove).
+    ///
+    /// # Panics
+    ///
+    /// - If cannot get the primary monitor's video mode in fullscreen mode.
+    pub fn create_window(&mut self, opt: Options, handler: T) -> Result<usize, DisplayError> {
+        let mut glfw = self.root.glfw.clone();
+        let display = Display::new_shared(&mut glfw, opt, handler, &self.root)?;
+        self.displays.push(display);
+        Ok(self.displays.len() - 1)
+    }
+
+    /// Removes the display at the given index and returns it, which still
+    /// shares the objects after the group is dropped. Its window is closed
+    /// when it is dropped, like any display's.
+    ///
+    /// # Panics
+    ///
+    /// - If the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Display<T> {
+        self.displays.remove(index)
+    }
+
+    /// Drops the displays whose windows should close, like after the close
+    /// buttons are clicked, which closes their windows. The shared objects
+    /// stay alive with the root.
+    pub fn remove_closed(&mut self) {
+        self.displays
+            .retain(|display| !display.window().should_close());
+    }
+
+    /// Returns the display at the given index.
+    pub fn get(&self, index: usize) -> Option<&Display<T>> {
+        self.displays.get(index)
+    }
+
+    /// Returns the display at the given index.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Display<T>> {
+        self.displays.get_mut(index)
+    }
+
+    /// Returns the displays in the order they were created.
+    pub fn displays(&self) -> &[Display<T>] {
+        &self.displays
+    }
+
+    /// Returns the displays in the order they were created.
+    pub fn displays_mut(&mut self) -> &mut [Display<T>] {
+        &mut self.displays
+    }
+
+    /// Returns the amount of displays, without the root.
+    pub fn len(&self) -> usize {
+        self.displays.len()
+    }
+
+    /// Returns whether there are no displays.
+    pub fn is_empty(&self) -> bool {
+        self.displays.is_empty()
+    }
+
+    /// Returns the hidden window of the root context, which can be made
+    /// current for loading the shared objects while there are no displays.
+    pub fn root(&self) -> &Window {
+        &self.root
+    }
+
+    /// Returns the hidden window of the root context mutably.
+    pub fn root_mut(&mut self) -> &mut Window {
+        &mut self.root
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Drop for ContextGroup<T> {
+    fn drop(&mut self) {
+        while let Some(display) = self.displays.pop() {
+            drop(display);
+        }
+        crate::gl::forget_blitter(self.root.window_ptr());
+    }
+}
@@ -2,6 +2,47 @@ pub use self::types::*;
 pub use self::enumerations::*;
 pub use self::functions::*;
 
+/// Vertex arrays and layouts, uniform and shader storage buffer objects, and
+/// the std140 layout.
+pub mod buffer;
+
+mod blit;
+pub use self::blit::*;
+
+mod get;
+pub use self::get::*;
+
+mod pass;
+pub use self::pass::*;
+
+/// Line drawing for visualizing during development.
+pub mod debug_draw;
+
+/// Offscreen render targets.
+pub mod framebuffer;
+
+/// Point sprite rendering of particles.
+pub mod particles;
+
+/// Post-processing effects, like the anti-aliasing.
+pub mod postprocess;
+
+/// GPU timing with query objects.
+pub mod query;
+
+/// Shader compilation, programs and their hot reloading.
+pub mod shader;
+
+/// Batched drawing of textured quads for 2D.
+pub mod sprite;
+
+/// Texture, texture array and cubemap objects.
+pub mod texture;
+
+/// Column-major matrix helpers for projections and cameras.
+#[cfg(feature = "math")]
+pub mod math;
+
 use std::os::raw::c_void;
 
 
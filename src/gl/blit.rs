@@ -0,0 +1,129 @@
+use std::cell::RefCell;
+
+use crate::gl::{self, buffer::Vao, shader::Program, texture::Texture2D};
+
+/// Vertex shader of a single triangle that covers the whole viewport, whose
+/// `uv` spans from 0 to 1 across it, drawn with 3 vertices and an empty
+/// vertex array.
+pub(crate) const FULLSCREEN_VERTEX_SHADER: &str = r"#version 460 core
+out vec2 uv;
+void main() {
+    vec2 position = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+    uv = position;
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
+";
+
+const FRAGMENT_SHADER: &str = r"#version 460 core
+in vec2 uv;
+uniform sampler2D image;
+out vec4 color;
+void main() {
+    color = texture(image, uv);
+}
+";
+
+/// Draws textures across the whole viewport, which is usually the whole
+/// framebuffer, with a single triangle that covers it.
+/// Meant for showing render targets and images, and for post-processing.
+/// Manages its own program and empty vertex array; thus, create one for each
+/// context, since the vertex arrays are not shared between them. Consider
+/// [blit_texture], which caches one for every context.
+pub struct Blitter {
+    program: Program,
+    vao: Vao,
+}
+
+impl Blitter {
+    /// Creates the blitter in the current context.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot build the internal shader.
+    pub fn new() -> Self {
+        let program = Program::new(FULLSCREEN_VERTEX_SHADER, FRAGMENT_SHADER)
+            .expect("Could not build the blit shader!");
+        Self {
+            program,
+            vao: Vao::new(),
+        }
+    }
+
+    /// Draws the texture across the whole viewport of the bound framebuffer.
+    /// Keeps the bound program, vertex array, active texture unit and the
+    /// texture on unit 0 as they were. Depth testing and blending still
+    /// apply; thus, consider disabling them before.
+    pub fn blit(&self, texture: &Texture2D) {
+        draw_fullscreen(&self.program, &self.vao, texture);
+    }
+}
+
+impl Default for Blitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws the [fullscreen triangle](FULLSCREEN_VERTEX_SHADER) with the given
+/// program and empty vertex array, and the texture on unit 0. Keeps the bound
+/// program, vertex array, active texture unit and the texture on unit 0 as
+/// they were.
+pub(crate) fn draw_fullscreen(program: &Program, vao: &Vao, texture: &Texture2D) {
+    let previous_program = gl::get_integer(gl::CURRENT_PROGRAM) as u32;
+    let previous_vao = gl::get_integer(gl::VERTEX_ARRAY_BINDING) as u32;
+    let previous_unit = gl::get_integer(gl::ACTIVE_TEXTURE) as u32;
+    gl::ActiveTexture(gl::TEXTURE0);
+    let previous_texture = gl::get_integer(gl::TEXTURE_BINDING_2D) as u32;
+    texture.bind(0);
+    program.bind();
+    vao.bind();
+    gl::DrawArrays(gl::TRIANGLES, 0, 3);
+    gl::BindVertexArray(previous_vao);
+    gl::UseProgram(previous_program);
+    gl::BindTexture(gl::TEXTURE_2D, previous_texture);
+    gl::ActiveTexture(previous_unit);
+}
+
+thread_local! {
+    /// Blitters of [blit_texture] with the contexts they were created in.
+    static BLITTERS: RefCell<Vec<(*mut glfw::ffi::GLFWwindow, Blitter)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Draws the texture across the whole viewport with the [Blitter] of the
+/// current context, which is created the first time in every context and
+/// cached until the [Display](crate::Display) of the context is dropped.
+/// Keeps the bound program, vertex array, active texture unit and the
+/// texture on unit 0 as they were. Depth testing and blending still apply;
+/// thus, consider disabling them before.
+///
+/// # Panics
+///
+/// - If cannot build the internal shader the first time in a context.
+pub fn blit_texture(texture: &Texture2D) {
+    let context = unsafe { glfw::ffi::glfwGetCurrentContext() };
+    BLITTERS.with(|blitters| {
+        let mut blitters = blitters.borrow_mut();
+        let index = match blitters.iter().position(|(cached, _)| *cached == context) {
+            Some(index) => index,
+            None => {
+                blitters.push((context, Blitter::new()));
+                blitters.len() - 1
+            }
+        };
+        blitters[index].1.blit(texture);
+    });
+}
+
+/// Forgets the cached [Blitter] of the given context, which is about to be
+/// destroyed with its objects; thus, a later context at the same address
+/// does not reuse them. The blitter is leaked rather than dropped, since its
+/// context may not be the current one.
+pub(crate) fn forget_blitter(context: *mut glfw::ffi::GLFWwindow) {
+    BLITTERS.with(|blitters| {
+        let mut blitters = blitters.borrow_mut();
+        if let Some(index) = blitters.iter().position(|(cached, _)| *cached == context) {
+            std::mem::forget(blitters.swap_remove(index));
+        }
+    });
+}
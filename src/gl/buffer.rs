@@ -0,0 +1,638 @@
+use std::{mem, os::raw::c_void, ptr};
+
+use crate::gl;
+
+/// Vertex array object, which stores the vertex attribute setup.
+/// Deleted when dropped.
+pub struct Vao {
+    id: u32,
+}
+
+impl Vao {
+    /// Generates a new vertex array object.
+    pub fn new() -> Self {
+        let mut id = 0;
+        gl::GenVertexArrays(1, &mut id);
+        Self { id }
+    }
+
+    /// Returns the OpenGL name of the vertex array object.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Binds the vertex array object.
+    pub fn bind(&self) {
+        gl::BindVertexArray(self.id);
+    }
+
+    /// Binds and draws `count` vertices starting from `first`.
+    pub fn draw_arrays(&self, mode: u32, first: i32, count: i32) {
+        self.bind();
+        gl::DrawArrays(mode, first, count);
+    }
+
+    /// Binds and draws `count` vertices starting from `first`, `instances`
+    /// times, like with the transforms of an [InstanceBuffer].
+    pub fn draw_instanced(&self, mode: u32, first: i32, count: i32, instances: i32) {
+        self.bind();
+        gl::DrawArraysInstanced(mode, first, count, instances);
+    }
+
+    /// Binds and draws a batch of vertex ranges in a single call.
+    /// Each range starts from the vertex in `firsts` and has the vertex count
+    /// at the same index in `counts`.
+    /// Uses `glMultiDrawArrays`, which is core since OpenGL 1.4; thus, it is
+    /// available on all drivers.
+    ///
+    /// # Panics
+    ///
+    /// - If the slices do not have the same length.
+    pub fn multi_draw_arrays(&self, mode: u32, firsts: &[i32], counts: &[i32]) {
+        assert_eq!(
+            firsts.len(),
+            counts.len(),
+            "Firsts and counts must have the same length!"
+        );
+        self.bind();
+        gl::MultiDrawArrays(mode, firsts.as_ptr(), counts.as_ptr(), firsts.len() as i32);
+    }
+
+    /// Binds and draws a batch of vertex ranges, with every range drawn
+    /// `instances` times.
+    /// There is no core multi draw call for instancing without an indirect
+    /// buffer, so this issues a `glDrawArraysInstanced` for each range.
+    ///
+    /// # Panics
+    ///
+    /// - If the slices do not have the same length.
+    pub fn multi_draw_arrays_instanced(
+        &self,
+        mode: u32,
+        firsts: &[i32],
+        counts: &[i32],
+        instances: i32,
+    ) {
+        assert_eq!(
+            firsts.len(),
+            counts.len(),
+            "Firsts and counts must have the same length!"
+        );
+        self.bind();
+        for (&first, &count) in firsts.iter().zip(counts) {
+            gl::DrawArraysInstanced(mode, first, count, instances);
+        }
+    }
+}
+
+impl Default for Vao {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Vao {
+    fn drop(&mut self) {
+        gl::DeleteVertexArrays(1, &self.id);
+    }
+}
+
+/// Vertex buffer of a `mat4` per instance, like the model matrices of the
+/// many copies of a mesh, which is drawn with [Vao::draw_instanced].
+/// A `mat4` attribute takes four locations, one for each `vec4` column; thus,
+/// `layout(location = 3) in mat4 model;` takes the locations from 3 to 6,
+/// which are set up to advance once per instance. Deleted when dropped.
+pub struct InstanceBuffer {
+    id: u32,
+    location: u32,
+    len: usize,
+}
+
+impl InstanceBuffer {
+    /// Creates an empty buffer, and sets up the attribute of the given vertex
+    /// array at the given location and the three after it.
+    /// Keeps the bound vertex array and `gl::ARRAY_BUFFER` as they were.
+    pub fn new(vao: &Vao, location: u32) -> Self {
+        let previous_vao = gl::get_integer(gl::VERTEX_ARRAY_BINDING) as u32;
+        let previous_buffer = gl::get_integer(gl::ARRAY_BUFFER_BINDING) as u32;
+        let mut id = 0;
+        gl::GenBuffers(1, &mut id);
+        vao.bind();
+        gl::BindBuffer(gl::ARRAY_BUFFER, id);
+        let stride = mem::size_of::<[f32; 16]>() as i32;
+        for column in 0..4 {
+            let offset = (column * mem::size_of::<[f32; 4]>()) as *const c_void;
+            gl::EnableVertexAttribArray(location + column as u32);
+            gl::VertexAttribPointer(
+                location + column as u32,
+                4,
+                gl::FLOAT,
+                gl::FALSE,
+                stride,
+                offset,
+            );
+            gl::VertexAttribDivisor(location + column as u32, 1);
+        }
+        gl::BindVertexArray(previous_vao);
+        gl::BindBuffer(gl::ARRAY_BUFFER, previous_buffer);
+        Self {
+            id,
+            location,
+            len: 0,
+        }
+    }
+
+    /// Returns the OpenGL name of the buffer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the first of the four locations of the attribute.
+    pub fn location(&self) -> u32 {
+        self.location
+    }
+
+    /// Returns the amount of the uploaded transforms, which is the amount of
+    /// the instances to draw.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether there are no uploaded transforms.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Replaces the contents with the given column-major matrices.
+    /// Reallocates the buffer every time, which lets the driver keep using
+    /// the old contents for the draws in flight instead of waiting for them.
+    /// Keeps the bound `gl::ARRAY_BUFFER` as it was.
+    pub fn upload(&mut self, transforms: &[[f32; 16]]) {
+        let previous_buffer = gl::get_integer(gl::ARRAY_BUFFER_BINDING) as u32;
+        gl::BindBuffer(gl::ARRAY_BUFFER, self.id);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            mem::size_of_val(transforms) as isize,
+            transforms.as_ptr() as *const c_void,
+            gl::DYNAMIC_DRAW,
+        );
+        gl::BindBuffer(gl::ARRAY_BUFFER, previous_buffer);
+        self.len = transforms.len();
+    }
+
+    /// Reads the uploaded transforms back from the GPU, like
+    /// [read_back_range], for checking what the instances are drawn with.
+    pub fn read_back(&self) -> Vec<[f32; 16]> {
+        read_back_range(self.id, 0, self.len * 16)
+            .chunks_exact(16)
+            .map(|matrix| matrix.try_into().unwrap())
+            .collect()
+    }
+}
+
+impl Drop for InstanceBuffer {
+    fn drop(&mut self) {
+        gl::DeleteBuffers(1, &self.id);
+    }
+}
+
+/// Reads the whole contents of the buffer with the given name back from the
+/// GPU as floats, like the vertices of a vertex buffer, for checking what
+/// reached the GPU when a draw shows nothing. Any trailing bytes that do not
+/// make a whole float are not read.
+/// **Stalls the pipeline** until the commands that write to the buffer are
+/// done, and copies the whole buffer; thus, meant for debugging rather than
+/// for every frame.
+pub fn read_back(buffer: u32) -> Vec<f32> {
+    let size = buffer_size(buffer);
+    read_back_range(buffer, 0, size / mem::size_of::<f32>())
+}
+
+/// Reads the given amount of floats starting from the given float offset back
+/// from the buffer with the given name, like [read_back] but only a part of
+/// it, which is cheaper for the large buffers.
+/// Keeps the bound `gl::COPY_READ_BUFFER` as it was.
+///
+/// # Panics
+///
+/// - If the range is not in the buffer.
+pub fn read_back_range(buffer: u32, offset: usize, len: usize) -> Vec<f32> {
+    let float = mem::size_of::<f32>();
+    assert!(
+        offset
+            .checked_add(len)
+            .and_then(|end| end.checked_mul(float))
+            .is_some_and(|end| end <= buffer_size(buffer)),
+        "Range is not in the buffer!"
+    );
+    let previous = gl::get_integer(gl::COPY_READ_BUFFER_BINDING) as u32;
+    let mut data = vec![0.0f32; len];
+    gl::BindBuffer(gl::COPY_READ_BUFFER, buffer);
+    gl::GetBufferSubData(
+        gl::COPY_READ_BUFFER,
+        (offset * float) as isize,
+        (len * float) as isize,
+        data.as_mut_ptr() as *mut c_void,
+    );
+    gl::BindBuffer(gl::COPY_READ_BUFFER, previous);
+    data
+}
+
+/// Returns the size of the buffer with the given name in bytes.
+fn buffer_size(buffer: u32) -> usize {
+    let previous = gl::get_integer(gl::COPY_READ_BUFFER_BINDING) as u32;
+    gl::BindBuffer(gl::COPY_READ_BUFFER, buffer);
+    let mut size = 0;
+    gl::GetBufferParameteri64v(gl::COPY_READ_BUFFER, gl::BUFFER_SIZE, &mut size);
+    gl::BindBuffer(gl::COPY_READ_BUFFER, previous);
+    size.max(0) as usize
+}
+
+/// Layout of the vertex attributes that are interleaved in a vertex buffer,
+/// which is built attribute by attribute in the order of the vertex struct's
+/// fields. The offsets and the stride are computed like a `#[repr(C)]` struct
+/// of arrays, like `{ position: [f32; 3], color: [u8; 4] }`.
+///
+/// # Panics
+///
+/// - If an attribute does not have between 1 and 4 components.
+#[derive(Clone, Debug, Default)]
+pub struct VertexLayout {
+    attribs: Vec<Attrib>,
+    size: usize,
+    alignment: usize,
+}
+
+/// Vertex attribute of a [VertexLayout].
+#[derive(Clone, Copy, Debug)]
+struct Attrib {
+    location: u32,
+    count: i32,
+    kind: u32,
+    normalized: bool,
+    integer: bool,
+    offset: usize,
+}
+
+impl VertexLayout {
+    /// Creates an empty layout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `float` attribute with the given component count, which is a
+    /// `vecN` in the shader.
+    pub fn attrib_f32(self, location: u32, count: i32) -> Self {
+        self.attrib(location, count, gl::FLOAT, 4, false, false)
+    }
+
+    /// Adds an unsigned byte attribute with the given component count, which
+    /// is normalized to `[0, 1]` and is a `vecN` in the shader, like colors.
+    pub fn attrib_u8_norm(self, location: u32, count: i32) -> Self {
+        self.attrib(location, count, gl::UNSIGNED_BYTE, 1, true, false)
+    }
+
+    /// Adds an `int` attribute with the given component count, which is an
+    /// `ivecN` in the shader.
+    pub fn attrib_i32(self, location: u32, count: i32) -> Self {
+        self.attrib(location, count, gl::INT, 4, false, true)
+    }
+
+    /// Adds an `uint` attribute with the given component count, which is an
+    /// `uvecN` in the shader.
+    pub fn attrib_u32(self, location: u32, count: i32) -> Self {
+        self.attrib(location, count, gl::UNSIGNED_INT, 4, false, true)
+    }
+
+    /// Returns the size of a vertex in bytes.
+    pub fn stride(&self) -> usize {
+        self.size.div_ceil(self.alignment.max(1)) * self.alignment.max(1)
+    }
+
+    /// Enables and points the attributes to the buffer that is bound to
+    /// `gl::ARRAY_BUFFER`, which is recorded by the bound [Vao].
+    pub fn apply(&self) {
+        let stride = self.stride() as i32;
+        for attrib in &self.attribs {
+            gl::EnableVertexAttribArray(attrib.location);
+            let offset = attrib.offset as *const c_void;
+            if attrib.integer {
+                gl::VertexAttribIPointer(
+                    attrib.location,
+                    attrib.count,
+                    attrib.kind,
+                    stride,
+                    offset,
+                );
+            } else {
+                gl::VertexAttribPointer(
+                    attrib.location,
+                    attrib.count,
+                    attrib.kind,
+                    attrib.normalized as u8,
+                    stride,
+                    offset,
+                );
+            }
+        }
+    }
+
+    fn attrib(
+        mut self,
+        location: u32,
+        count: i32,
+        kind: u32,
+        size: usize,
+        normalized: bool,
+        integer: bool,
+    ) -> Self {
+        assert!(
+            (1..=4).contains(&count),
+            "Attribute count must be between 1 and 4!"
+        );
+        let offset = self.size.div_ceil(size) * size;
+        self.attribs.push(Attrib {
+            location,
+            count,
+            kind,
+            normalized,
+            integer,
+            offset,
+        });
+        self.size = offset + size * count as usize;
+        self.alignment = self.alignment.max(size);
+        self
+    }
+}
+
+/// Uniform buffer object, which shares uniform block data between programs,
+/// like the camera matrices. Deleted when dropped.
+pub struct Ubo {
+    id: u32,
+    size: usize,
+}
+
+impl Ubo {
+    /// Creates a uniform buffer of the given size in bytes with undefined
+    /// contents. Keeps the bound `gl::UNIFORM_BUFFER` as it was.
+    pub fn new(size: usize) -> Self {
+        let previous = gl::get_integer(gl::UNIFORM_BUFFER_BINDING) as u32;
+        let mut id = 0;
+        gl::GenBuffers(1, &mut id);
+        gl::BindBuffer(gl::UNIFORM_BUFFER, id);
+        gl::BufferData(
+            gl::UNIFORM_BUFFER,
+            size as isize,
+            ptr::null(),
+            gl::DYNAMIC_DRAW,
+        );
+        gl::BindBuffer(gl::UNIFORM_BUFFER, previous);
+        Self { id, size }
+    }
+
+    /// Returns the OpenGL name of the uniform buffer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the size in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Binds the buffer to the given uniform block binding point, which is
+    /// the `binding` in the shaders' `layout(std140, binding = 0) uniform`.
+    pub fn bind_base(&self, binding: u32) {
+        gl::BindBufferBase(gl::UNIFORM_BUFFER, binding, self.id);
+    }
+
+    /// Overwrites the bytes starting from the given offset.
+    /// Consider building the data with [Std140]. Keeps the bound
+    /// `gl::UNIFORM_BUFFER` as it was.
+    ///
+    /// # Panics
+    ///
+    /// - If the data does not fit in the buffer.
+    pub fn update(&self, offset: usize, data: &[u8]) {
+        assert!(
+            offset
+                .checked_add(data.len())
+                .is_some_and(|end| end <= self.size),
+            "Data does not fit in the uniform buffer!"
+        );
+        let previous = gl::get_integer(gl::UNIFORM_BUFFER_BINDING) as u32;
+        gl::BindBuffer(gl::UNIFORM_BUFFER, self.id);
+        gl::BufferSubData(
+            gl::UNIFORM_BUFFER,
+            offset as isize,
+            data.len() as isize,
+            data.as_ptr() as *const c_void,
+        );
+        gl::BindBuffer(gl::UNIFORM_BUFFER, previous);
+    }
+}
+
+impl Drop for Ubo {
+    fn drop(&mut self) {
+        gl::DeleteBuffers(1, &self.id);
+    }
+}
+
+/// Shader storage buffer object, which can be read and written by the
+/// shaders, like the inputs and the results of the
+/// [compute shaders](crate::gl::shader::compute) or large instance data.
+/// Deleted when dropped.
+pub struct Ssbo {
+    id: u32,
+    size: usize,
+}
+
+impl Ssbo {
+    /// Creates a shader storage buffer of the given size in bytes with
+    /// undefined contents. Keeps the bound `gl::SHADER_STORAGE_BUFFER` as it
+    /// was, like the other methods.
+    pub fn new(size: usize) -> Self {
+        let previous = gl::get_integer(gl::SHADER_STORAGE_BUFFER_BINDING) as u32;
+        let mut id = 0;
+        gl::GenBuffers(1, &mut id);
+        gl::BindBuffer(gl::SHADER_STORAGE_BUFFER, id);
+        gl::BufferData(
+            gl::SHADER_STORAGE_BUFFER,
+            size as isize,
+            ptr::null(),
+            gl::DYNAMIC_COPY,
+        );
+        gl::BindBuffer(gl::SHADER_STORAGE_BUFFER, previous);
+        Self { id, size }
+    }
+
+    /// Returns the OpenGL name of the shader storage buffer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the size in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Binds the buffer to the given shader storage block binding point, which
+    /// is the `binding` in the shaders' `layout(std430, binding = 0) buffer`.
+    pub fn bind_base(&self, binding: u32) {
+        gl::BindBufferBase(gl::SHADER_STORAGE_BUFFER, binding, self.id);
+    }
+
+    /// Overwrites the bytes starting from the given offset.
+    ///
+    /// # Panics
+    ///
+    /// - If the data does not fit in the buffer.
+    pub fn update(&self, offset: usize, data: &[u8]) {
+        assert!(
+            offset
+                .checked_add(data.len())
+                .is_some_and(|end| end <= self.size),
+            "Data does not fit in the shader storage buffer!"
+        );
+        let previous = gl::get_integer(gl::SHADER_STORAGE_BUFFER_BINDING) as u32;
+        gl::BindBuffer(gl::SHADER_STORAGE_BUFFER, self.id);
+        gl::BufferSubData(
+            gl::SHADER_STORAGE_BUFFER,
+            offset as isize,
+            data.len() as isize,
+            data.as_ptr() as *const c_void,
+        );
+        gl::BindBuffer(gl::SHADER_STORAGE_BUFFER, previous);
+    }
+
+    /// Reads the given amount of bytes starting from the given offset back
+    /// from the GPU, which waits for the commands that write to the buffer.
+    /// The writes of the shaders need a
+    /// [memory barrier](crate::Display::memory_barrier) with
+    /// `gl::BUFFER_UPDATE_BARRIER_BIT` before.
+    ///
+    /// # Panics
+    ///
+    /// - If the range is not in the buffer.
+    pub fn read_back(&self, offset: usize, len: usize) -> Vec<u8> {
+        assert!(
+            offset.checked_add(len).is_some_and(|end| end <= self.size),
+            "Range is not in the shader storage buffer!"
+        );
+        let mut data = vec![0u8; len];
+        let previous = gl::get_integer(gl::SHADER_STORAGE_BUFFER_BINDING) as u32;
+        gl::BindBuffer(gl::SHADER_STORAGE_BUFFER, self.id);
+        gl::GetBufferSubData(
+            gl::SHADER_STORAGE_BUFFER,
+            offset as isize,
+            len as isize,
+            data.as_mut_ptr() as *mut c_void,
+        );
+        gl::BindBuffer(gl::SHADER_STORAGE_BUFFER, previous);
+        data
+    }
+}
+
+impl Drop for Ssbo {
+    fn drop(&mut self) {
+        gl::DeleteBuffers(1, &self.id);
+    }
+}
+
+/// Builder of uniform block data in the `std140` layout, which is pushed
+/// member by member in the order of the block's declaration.
+///
+/// The rules of the layout are:
+/// - `float` and `int` are aligned to 4 bytes.
+/// - `vec2` is aligned to 8 bytes.
+/// - `vec3` and `vec4` are aligned to 16 bytes; thus, a `vec3` is padded like
+///   a `vec4` unless a scalar follows it.
+/// - `mat4` is four `vec4` columns, aligned to 16 bytes.
+/// - Array elements and structs are aligned to 16 bytes, which is why arrays of
+///   scalars waste 12 bytes per element; push them as `vec4`s instead.
+#[derive(Clone, Debug, Default)]
+pub struct Std140 {
+    bytes: Vec<u8>,
+}
+
+impl Std140 {
+    /// Creates an empty block.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pushes a `float`.
+    pub fn push_f32(&mut self, value: f32) -> &mut Self {
+        self.push(4, &[value])
+    }
+
+    /// Pushes an `int`.
+    pub fn push_i32(&mut self, value: i32) -> &mut Self {
+        self.align(4);
+        self.bytes.extend_from_slice(&value.to_ne_bytes());
+        self
+    }
+
+    /// Pushes a `vec2`.
+    pub fn push_vec2(&mut self, value: [f32; 2]) -> &mut Self {
+        self.push(8, &value)
+    }
+
+    /// Pushes a `vec3`.
+    pub fn push_vec3(&mut self, value: [f32; 3]) -> &mut Self {
+        self.push(16, &value)
+    }
+
+    /// Pushes a `vec4`.
+    pub fn push_vec4(&mut self, value: [f32; 4]) -> &mut Self {
+        self.push(16, &value)
+    }
+
+    /// Pushes a `mat4` from a column-major matrix.
+    pub fn push_mat4(&mut self, value: &[f32; 16]) -> &mut Self {
+        self.push(16, value)
+    }
+
+    /// Returns the data, which can be passed to [Ubo::update].
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn push(&mut self, alignment: usize, values: &[f32]) -> &mut Self {
+        self.align(alignment);
+        for value in values {
+            self.bytes.extend_from_slice(&value.to_ne_bytes());
+        }
+        self
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let len = self.bytes.len().div_ceil(alignment) * alignment;
+        self.bytes.resize(len, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vertex_layout_matches_repr_c() {
+        let layout = VertexLayout::new()
+            .attrib_u8_norm(0, 3)
+            .attrib_f32(1, 2)
+            .attrib_u8_norm(2, 1);
+        let offsets: Vec<_> = layout.attribs.iter().map(|a| a.offset).collect();
+        assert_eq!(offsets, [0, 4, 12]);
+        assert_eq!(layout.stride(), 16);
+    }
+
+    #[test]
+    fn std140_aligns_members() {
+        let mut block = Std140::new();
+        block.push_f32(1.0).push_vec3([2.0; 3]).push_f32(3.0);
+        assert_eq!(block.bytes().len(), 32);
+        block.push_vec2([4.0; 2]).push_mat4(&[5.0; 16]);
+        assert_eq!(block.bytes().len(), 48 + 64);
+    }
+}
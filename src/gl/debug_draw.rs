@@ -0,0 +1,152 @@
+use std::{mem, os::raw::c_void};
+
+use crate::gl::{self, buffer::Vao, shader::Program};
+
+const VERTEX_SHADER: &str = r"#version 460 core
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec4 color;
+uniform mat4 view_projection;
+out vec4 frag_color;
+void main() {
+    gl_Position = view_projection * vec4(position, 1.0);
+    frag_color = color;
+}
+";
+
+const FRAGMENT_SHADER: &str = r"#version 460 core
+in vec4 frag_color;
+out vec4 color;
+void main() {
+    color = frag_color;
+}
+";
+
+/// Position and color, in this order.
+const FLOATS_PER_VERTEX: usize = 7;
+
+/// Color of the [grid](DebugDraw::grid) lines.
+const GRID_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+
+/// Buffers colored line segments for visualizing axes, bounding boxes and
+/// grids during development, and draws them in a single call.
+/// Manages its own vertex array, vertex buffer and shader.
+pub struct DebugDraw {
+    program: Program,
+    vao: Vao,
+    vbo: u32,
+    vertices: Vec<f32>,
+}
+
+impl DebugDraw {
+    /// Creates an empty buffer of lines with its own buffers and shader.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot build the internal shader.
+    pub fn new() -> Self {
+        let program = Program::new(VERTEX_SHADER, FRAGMENT_SHADER)
+            .expect("Could not build the debug draw shader!");
+        let vao = Vao::new();
+        vao.bind();
+        let mut vbo = 0;
+        gl::GenBuffers(1, &mut vbo);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        let stride = (FLOATS_PER_VERTEX * mem::size_of::<f32>()) as i32;
+        for (index, (size, offset)) in [(3, 0), (4, 3)].into_iter().enumerate() {
+            let offset = offset * mem::size_of::<f32>();
+            gl::EnableVertexAttribArray(index as u32);
+            gl::VertexAttribPointer(
+                index as u32,
+                size,
+                gl::FLOAT,
+                gl::FALSE,
+                stride,
+                offset as *const c_void,
+            );
+        }
+        gl::BindVertexArray(0);
+        Self {
+            program,
+            vao,
+            vbo,
+            vertices: Vec::new(),
+        }
+    }
+
+    /// Adds a line segment between the points with the RGBA color.
+    pub fn line(&mut self, p0: [f32; 3], p1: [f32; 3], color: [f32; 4]) {
+        for [x, y, z] in [p0, p1] {
+            self.vertices.extend_from_slice(&[x, y, z]);
+            self.vertices.extend_from_slice(&color);
+        }
+    }
+
+    /// Adds a grid on the XZ plane, which is the ground when Y is up, centered
+    /// at the origin. The grid is `size` long on both axes with a line every
+    /// `step`, and its center lines are the X axis in red and the Z axis in
+    /// blue.
+    pub fn grid(&mut self, size: f32, step: f32) {
+        let half = size / 2.0;
+        let lines = (half / step).floor() as i32;
+        for i in -lines..=lines {
+            let at = i as f32 * step;
+            let (x_color, z_color) = if i == 0 {
+                ([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0])
+            } else {
+                (GRID_COLOR, GRID_COLOR)
+            };
+            self.line([-half, 0.0, at], [half, 0.0, at], x_color);
+            self.line([at, 0.0, -half], [at, 0.0, half], z_color);
+        }
+    }
+
+    /// Adds the edges of the axis aligned box between the corners.
+    pub fn aabb(&mut self, min: [f32; 3], max: [f32; 3], color: [f32; 4]) {
+        let corner = |i: usize| {
+            [
+                if i & 1 == 0 { min[0] } else { max[0] },
+                if i & 2 == 0 { min[1] } else { max[1] },
+                if i & 4 == 0 { min[2] } else { max[2] },
+            ]
+        };
+        for i in 0..8 {
+            for axis in [1, 2, 4] {
+                if i & axis == 0 {
+                    self.line(corner(i), corner(i | axis), color);
+                }
+            }
+        }
+    }
+
+    /// Draws the buffered lines with the given column-major view-projection
+    /// matrix in a single call, and empties the buffer.
+    pub fn flush(&mut self, view_projection: &[f32; 16]) {
+        if self.vertices.is_empty() {
+            return;
+        }
+        self.program.bind();
+        self.program.set_mat4("view_projection", view_projection);
+        gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            mem::size_of_val(self.vertices.as_slice()) as isize,
+            self.vertices.as_ptr() as *const c_void,
+            gl::STREAM_DRAW,
+        );
+        let count = (self.vertices.len() / FLOATS_PER_VERTEX) as i32;
+        self.vao.draw_arrays(gl::LINES, 0, count);
+        self.vertices.clear();
+    }
+}
+
+impl Default for DebugDraw {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for DebugDraw {
+    fn drop(&mut self) {
+        gl::DeleteBuffers(1, &self.vbo);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::gl::{self, texture::Texture2D};
+
+/// Color attachment of a [Framebuffer].
+enum Color {
+    Texture(Texture2D),
+    Renderbuffer(u32),
+}
+
+/// Offscreen render target with a single RGBA8 color attachment.
+/// Deleted with its attachment when dropped.
+pub struct Framebuffer {
+    id: u32,
+    color: Color,
+    width: u32,
+    height: u32,
+    samples: Option<u32>,
+}
+
+impl Framebuffer {
+    /// Creates a framebuffer that renders to a [Texture2D] of the given size,
+    /// which can be sampled afterwards.
+    ///
+    /// # Panics
+    ///
+    /// - If the framebuffer is not complete.
+    pub fn new(width: u32, height: u32) -> Self {
+        let texture = Texture2D::new(width, height);
+        Self::with_color(width, height, None, |target| {
+            gl::FramebufferTexture2D(
+                target,
+                gl::COLOR_ATTACHMENT0,
+                gl::TEXTURE_2D,
+                texture.id(),
+                0,
+            );
+            Color::Texture(texture)
+        })
+    }
+
+    /// Creates a framebuffer that renders to a multisampled renderbuffer of the
+    /// given size, which cannot be sampled; rather, it must be
+    /// [resolved](Self::resolve_to) to a regular framebuffer.
+    ///
+    /// # Panics
+    ///
+    /// - If the framebuffer is not complete, which can happen if the samples
+    ///   are more than `GL_MAX_SAMPLES`.
+    pub fn new_multisampled(width: u32, height: u32, samples: u32) -> Self {
+        let mut renderbuffer = 0;
+        gl::GenRenderbuffers(1, &mut renderbuffer);
+        gl::BindRenderbuffer(gl::RENDERBUFFER, renderbuffer);
+        gl::RenderbufferStorageMultisample(
+            gl::RENDERBUFFER,
+            samples as i32,
+            gl::RGBA8,
+            width as i32,
+            height as i32,
+        );
+        Self::with_color(width, height, Some(samples), |target| {
+            gl::FramebufferRenderbuffer(
+                target,
+                gl::COLOR_ATTACHMENT0,
+                gl::RENDERBUFFER,
+                renderbuffer,
+            );
+            Color::Renderbuffer(renderbuffer)
+        })
+    }
+
+    fn with_color(
+        width: u32,
+        height: u32,
+        samples: Option<u32>,
+        attach: impl FnOnce(u32) -> Color,
+    ) -> Self {
+        let previous = gl::get_integer(gl::FRAMEBUFFER_BINDING) as u32;
+        let mut id = 0;
+        gl::GenFramebuffers(1, &mut id);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, id);
+        let color = attach(gl::FRAMEBUFFER);
+        let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, previous);
+        let framebuffer = Self {
+            id,
+            color,
+            width,
+            height,
+            samples,
+        };
+        assert_eq!(
+            status,
+            gl::FRAMEBUFFER_COMPLETE,
+            "Framebuffer is not complete!"
+        );
+        framebuffer
+    }
+
+    /// Returns the OpenGL name of the framebuffer.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the amount of samples if the framebuffer is multisampled.
+    pub fn samples(&self) -> Option<u32> {
+        self.samples
+    }
+
+    /// Returns the color texture if the framebuffer is not multisampled.
+    pub fn texture(&self) -> Option<&Texture2D> {
+        match &self.color {
+            Color::Texture(texture) => Some(texture),
+            Color::Renderbuffer(_) => None,
+        }
+    }
+
+    /// Binds the framebuffer for both drawing and reading.
+    /// The viewport is not changed.
+    pub fn bind(&self) {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, self.id);
+    }
+
+    /// Binds the default framebuffer, which is the window's, for both drawing
+    /// and reading.
+    pub fn bind_default() {
+        gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+    }
+
+    /// Resolves the samples of this multisampled framebuffer to the given
+    /// regular framebuffer, so the result can be sampled from its texture.
+    /// Keeps the framebuffer bindings as they were.
+    ///
+    /// # Panics
+    ///
+    /// - If this framebuffer is not multisampled.
+    /// - If the target is multisampled.
+    /// - If the sizes do not match.
+    pub fn resolve_to(&self, target: &Framebuffer) {
+        assert!(
+            self.samples.is_some(),
+            "Resolved framebuffer must be multisampled!"
+        );
+        assert!(
+            target.samples.is_none(),
+            "Resolve target must not be multisampled!"
+        );
+        assert!(
+            self.width == target.width && self.height == target.height,
+            "Resolved framebuffer and the target must have the same size!"
+        );
+        let read = gl::get_integer(gl::READ_FRAMEBUFFER_BINDING) as u32;
+        let draw = gl::get_integer(gl::DRAW_FRAMEBUFFER_BINDING) as u32;
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.id);
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, target.id);
+        let (width, height) = (self.width as i32, self.height as i32);
+        gl::BlitFramebuffer(
+            0,
+            0,
+            width,
+            height,
+            0,
+            0,
+            width,
+            height,
+            gl::COLOR_BUFFER_BIT,
+            gl::NEAREST,
+        );
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, read);
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, draw);
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        gl::DeleteFramebuffers(1, &self.id);
+        if let Color::Renderbuffer(renderbuffer) = self.color {
+            gl::DeleteRenderbuffers(1, &renderbuffer);
+        }
+    }
+}
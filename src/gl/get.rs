@@ -0,0 +1,27 @@
+use crate::gl;
+
+/// Returns the integer state with the given name, like `gl::MAX_SAMPLES` or
+/// `gl::FRAMEBUFFER_BINDING`, which must have a single value.
+pub fn get_integer(pname: u32) -> i32 {
+    let mut value = 0;
+    gl::GetIntegerv(pname, &mut value);
+    value
+}
+
+/// Returns the integer state with the given name, like `gl::VIEWPORT`, which
+/// must have exactly `count` values.
+/// The caller is responsible for the count, since it is not checked; a
+/// smaller one makes OpenGL write past the end of the vector!
+pub fn get_integerv(pname: u32, count: usize) -> Vec<i32> {
+    let mut values = vec![0; count];
+    gl::GetIntegerv(pname, values.as_mut_ptr());
+    values
+}
+
+/// Returns the floating point state with the given name, like
+/// `gl::LINE_WIDTH`, which must have a single value.
+pub fn get_float(pname: u32) -> f32 {
+    let mut value = 0.0;
+    gl::GetFloatv(pname, &mut value);
+    value
+}
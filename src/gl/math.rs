@@ -0,0 +1,259 @@
+//! All matrices are `[f32; 16]` in column-major order, which is what
+//! `gl::UniformMatrix4fv` expects with `transpose` set to `FALSE`.
+
+/// 4x4 matrix in column-major order.
+pub type Mat4 = [f32; 16];
+
+/// 2 component vector.
+pub type Vec2 = [f32; 2];
+
+/// 3 component vector.
+pub type Vec3 = [f32; 3];
+
+/// Identity matrix.
+pub const IDENTITY: Mat4 = [
+    1.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0, //
+];
+
+/// Orthographic projection, same as the old `glOrtho`.
+/// Maps the given box to the normalized device coordinates.
+#[rustfmt::skip]
+pub fn ortho(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+    let w = right - left;
+    let h = top - bottom;
+    let d = far - near;
+    [
+        2.0 / w, 0.0, 0.0, 0.0,
+        0.0, 2.0 / h, 0.0, 0.0,
+        0.0, 0.0, -2.0 / d, 0.0,
+        -(right + left) / w, -(top + bottom) / h, -(far + near) / d, 1.0,
+    ]
+}
+
+/// Perspective projection, same as the old `gluPerspective`.
+/// Vertical field of view is in radians and aspect ratio is width/height.
+#[rustfmt::skip]
+pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+    let f = 1.0 / (fov_y / 2.0).tan();
+    let d = near - far;
+    [
+        f / aspect, 0.0, 0.0, 0.0,
+        0.0, f, 0.0, 0.0,
+        0.0, 0.0, (far + near) / d, -1.0,
+        0.0, 0.0, 2.0 * far * near / d, 0.0,
+    ]
+}
+
+/// View matrix of a camera at `eye` looking at `center`, same as the old
+/// `gluLookAt`. The `up` vector does not need to be normalized, but must not
+/// be parallel to the viewing direction.
+#[rustfmt::skip]
+pub fn look_at(eye: Vec3, center: Vec3, up: Vec3) -> Mat4 {
+    let f = normalize(sub(center, eye));
+    let s = normalize(cross(f, up));
+    let u = cross(s, f);
+    [
+        s[0], u[0], -f[0], 0.0,
+        s[1], u[1], -f[1], 0.0,
+        s[2], u[2], -f[2], 0.0,
+        -dot(s, eye), -dot(u, eye), dot(f, eye), 1.0,
+    ]
+}
+
+/// Matrix product `a * b`, thus `b` is applied first.
+pub fn mul(a: &Mat4, b: &Mat4) -> Mat4 {
+    let mut r = [0.0; 16];
+    for col in 0..4 {
+        for row in 0..4 {
+            r[col * 4 + row] = (0..4).map(|k| a[k * 4 + row] * b[col * 4 + k]).sum();
+        }
+    }
+    r
+}
+
+/// Camera for 2D scenes that pans, zooms and rotates.
+/// World units are framebuffer pixels at zoom 1, with the Y axis up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera2D {
+    /// Point of the world at the center of the viewport.
+    pub position: Vec2,
+    /// Pixels per world unit; larger shows less of the world.
+    pub zoom: f32,
+    /// Counterclockwise rotation of the camera in radians, which turns the
+    /// world clockwise on the screen.
+    pub rotation: f32,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            position: [0.0, 0.0],
+            zoom: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Camera2D {
+    /// Returns the matrix from the world to the normalized device coordinates
+    /// for a viewport of the given size in pixels. The depths are mapped like
+    /// [ortho] from -1 to 1.
+    #[rustfmt::skip]
+    pub fn view_proj(&self, viewport_w: f32, viewport_h: f32) -> Mat4 {
+        let (s, c) = self.rotation.sin_cos();
+        let sx = 2.0 * self.zoom / viewport_w;
+        let sy = 2.0 * self.zoom / viewport_h;
+        let [x, y] = self.position;
+        [
+            sx * c, -sy * s, 0.0, 0.0,
+            sx * s, sy * c, 0.0, 0.0,
+            0.0, 0.0, -1.0, 0.0,
+            -sx * (c * x + s * y), sy * (s * x - c * y), 0.0, 1.0,
+        ]
+    }
+
+    /// Returns the point of the world under the given position in pixels from
+    /// the top-left corner of a viewport of the given size, like the cursor
+    /// in framebuffer pixels.
+    pub fn screen_to_world(&self, x: f32, y: f32, viewport_w: f32, viewport_h: f32) -> Vec2 {
+        let (s, c) = self.rotation.sin_cos();
+        let dx = (x - viewport_w / 2.0) / self.zoom;
+        let dy = (viewport_h / 2.0 - y) / self.zoom;
+        [
+            self.position[0] + c * dx - s * dy,
+            self.position[1] + s * dx + c * dy,
+        ]
+    }
+
+    /// Moves the camera by the given movement in pixels on the screen, with
+    /// the Y axis down like the cursor; thus, dragging the cursor by its
+    /// movement drags the world with it.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        let (s, c) = self.rotation.sin_cos();
+        let (dx, dy) = (-dx / self.zoom, dy / self.zoom);
+        self.position[0] += c * dx - s * dy;
+        self.position[1] += s * dx + c * dy;
+    }
+
+    /// Multiplies the zoom by the given factor, while keeping the point of the
+    /// world under the given position in pixels at the same place, like the
+    /// cursor while scrolling.
+    pub fn zoom_at(&mut self, factor: f32, x: f32, y: f32, viewport_w: f32, viewport_h: f32) {
+        let before = self.screen_to_world(x, y, viewport_w, viewport_h);
+        self.zoom *= factor;
+        let after = self.screen_to_world(x, y, viewport_w, viewport_h);
+        self.position[0] += before[0] - after[0];
+        self.position[1] += before[1] - after[1];
+    }
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: Vec3) -> Vec3 {
+    let l = dot(a, a).sqrt();
+    [a[0] / l, a[1] / l, a[2] / l]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(m: &Mat4, p: [f32; 4]) -> [f32; 4] {
+        let mut r = [0.0; 4];
+        for (row, r) in r.iter_mut().enumerate() {
+            *r = (0..4).map(|k| m[k * 4 + row] * p[k]).sum();
+        }
+        r
+    }
+
+    fn assert_near(a: &[f32], b: &[f32]) {
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-5, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn ortho_maps_corners() {
+        let m = ortho(0.0, 1280.0, 720.0, 0.0, -1.0, 1.0);
+        assert_near(&transform(&m, [0.0, 0.0, 0.0, 1.0]), &[-1.0, 1.0, 0.0, 1.0]);
+        assert_near(
+            &transform(&m, [1280.0, 720.0, 0.0, 1.0]),
+            &[1.0, -1.0, 0.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn perspective_maps_near_and_far() {
+        let m = perspective(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 10.0);
+        let n = transform(&m, [0.0, 0.0, -1.0, 1.0]);
+        let f = transform(&m, [0.0, 0.0, -10.0, 1.0]);
+        assert_near(&[n[2] / n[3], f[2] / f[3]], &[-1.0, 1.0]);
+    }
+
+    fn camera() -> Camera2D {
+        Camera2D {
+            position: [100.0, 50.0],
+            zoom: 2.0,
+            rotation: 0.3,
+        }
+    }
+
+    #[test]
+    fn camera_position_is_at_center() {
+        let m = camera().view_proj(800.0, 600.0);
+        assert_near(
+            &transform(&m, [100.0, 50.0, 0.0, 1.0]),
+            &[0.0, 0.0, 0.0, 1.0],
+        );
+        assert_near(
+            &camera().screen_to_world(400.0, 300.0, 800.0, 600.0),
+            &[100.0, 50.0],
+        );
+    }
+
+    #[test]
+    fn screen_to_world_inverts_view_proj() {
+        let camera = camera();
+        let m = camera.view_proj(800.0, 600.0);
+        let [x, y] = camera.screen_to_world(200.0, 150.0, 800.0, 600.0);
+        assert_near(&transform(&m, [x, y, 0.0, 1.0]), &[-0.5, 0.5, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn zoom_at_keeps_point_under_cursor() {
+        let mut camera = camera();
+        let before = camera.screen_to_world(200.0, 150.0, 800.0, 600.0);
+        camera.zoom_at(1.5, 200.0, 150.0, 800.0, 600.0);
+        assert_near(&camera.screen_to_world(200.0, 150.0, 800.0, 600.0), &before);
+        let mut panned = camera;
+        panned.pan(10.0, -20.0);
+        assert_near(
+            &panned.screen_to_world(210.0, 130.0, 800.0, 600.0),
+            &camera.screen_to_world(200.0, 150.0, 800.0, 600.0),
+        );
+    }
+
+    #[test]
+    fn look_at_from_origin_is_identity() {
+        let m = look_at([0.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]);
+        assert_near(&m, &IDENTITY);
+        assert_near(&mul(&m, &IDENTITY), &IDENTITY);
+    }
+}
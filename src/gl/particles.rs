@@ -0,0 +1,122 @@
+use std::{mem, os::raw::c_void};
+
+use crate::gl::{
+    self,
+    buffer::{Vao, VertexLayout},
+    shader::Program,
+};
+
+const VERTEX_SHADER: &str = r"#version 460 core
+layout(location = 0) in vec3 position;
+layout(location = 1) in float size;
+layout(location = 2) in vec4 color;
+uniform mat4 view_projection;
+out vec4 frag_color;
+void main() {
+    gl_Position = view_projection * vec4(position, 1.0);
+    gl_PointSize = size;
+    frag_color = color;
+}
+";
+
+const FRAGMENT_SHADER: &str = r"#version 460 core
+in vec4 frag_color;
+out vec4 color;
+void main() {
+    float distance = length(gl_PointCoord - vec2(0.5));
+    if (distance > 0.5) {
+        discard;
+    }
+    color = vec4(frag_color.rgb, frag_color.a * smoothstep(0.5, 0.4, distance));
+}
+";
+
+/// Point of a [Particles] system, which is uploaded as it is.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Particle {
+    /// Position in the world.
+    pub position: [f32; 3],
+    /// Diameter in pixels, which does not shrink with the distance.
+    pub size: f32,
+    /// RGBA color, whose alpha fades the edge of the round sprite.
+    pub color: [f32; 4],
+}
+
+/// Draws many particles as round point sprites in a single call, like the
+/// sparks and the smoke. The simulation is done by the application, which
+/// uploads the particles every frame.
+/// Manages its own vertex array, vertex buffer and shader.
+pub struct Particles {
+    program: Program,
+    vao: Vao,
+    vbo: u32,
+    count: i32,
+}
+
+impl Particles {
+    /// Creates an empty particle system with its own buffers and shader.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot build the internal shader.
+    pub fn new() -> Self {
+        let program = Program::new(VERTEX_SHADER, FRAGMENT_SHADER)
+            .expect("Could not build the particle shader!");
+        let vao = Vao::new();
+        vao.bind();
+        let mut vbo = 0;
+        gl::GenBuffers(1, &mut vbo);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        VertexLayout::new()
+            .attrib_f32(0, 3)
+            .attrib_f32(1, 1)
+            .attrib_f32(2, 4)
+            .apply();
+        gl::BindVertexArray(0);
+        Self {
+            program,
+            vao,
+            vbo,
+            count: 0,
+        }
+    }
+
+    /// Replaces the particles that are drawn.
+    pub fn update(&mut self, particles: &[Particle]) {
+        gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            mem::size_of_val(particles) as isize,
+            particles.as_ptr() as *const c_void,
+            gl::STREAM_DRAW,
+        );
+        self.count = particles.len() as i32;
+    }
+
+    /// Draws the particles with the given column-major view-projection matrix
+    /// in a single call. Enables `gl::PROGRAM_POINT_SIZE`, which the sizes
+    /// need. Blending must be set up by the application, like additive
+    /// blending for the sparks.
+    pub fn draw(&self, view_projection: &[f32; 16]) {
+        if self.count == 0 {
+            return;
+        }
+        gl::Enable(gl::PROGRAM_POINT_SIZE);
+        self.program.bind();
+        self.program.set_mat4("view_projection", view_projection);
+        self.vao.draw_arrays(gl::POINTS, 0, self.count);
+    }
+}
+
+impl Default for Particles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Particles {
+    fn drop(&mut self) {
+        gl::DeleteBuffers(1, &self.vbo);
+    }
+}
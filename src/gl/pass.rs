@@ -0,0 +1,163 @@
+use glfw::WindowEvent;
+
+use crate::{
+    gl::{self, framebuffer::Framebuffer},
+    Display,
+};
+
+/// State that a [Pass] changes, which is restored when it ends.
+#[derive(Clone, Copy, Debug)]
+struct Saved {
+    draw_framebuffer: i32,
+    read_framebuffer: i32,
+    viewport: [i32; 4],
+    depth: bool,
+    depth_mask: bool,
+    blend: bool,
+    cull: bool,
+    blend_func: [i32; 4],
+    clear_color: [f32; 4],
+}
+
+/// Render pass, which sets up a render target with its viewport, the depth,
+/// blend and cull state and the clears when it [begins](Self::begin), and
+/// restores them when it [ends](Self::end).
+/// Replaces the "set up the state, draw, restore" ceremony around a group of
+/// draws, like `let pass = Pass::to_screen(&display).with_depth().begin();`
+/// followed by the draws and `pass.end();`.
+/// The capabilities that are not asked for are disabled during the pass.
+#[derive(Clone, Copy, Debug)]
+pub struct Pass {
+    framebuffer: u32,
+    size: (i32, i32),
+    depth: bool,
+    blend: bool,
+    cull: bool,
+    clear_color: Option<[f32; 4]>,
+    saved: Option<Saved>,
+}
+
+impl Pass {
+    /// Creates a pass that renders to the window of the display, with the
+    /// current size of its framebuffer.
+    pub fn to_screen<T: FnMut(WindowEvent)>(display: &Display<T>) -> Self {
+        Self::new(0, display.window().get_framebuffer_size())
+    }
+
+    /// Creates a pass that renders to the given framebuffer.
+    pub fn to_framebuffer(framebuffer: &Framebuffer) -> Self {
+        let size = (framebuffer.width() as i32, framebuffer.height() as i32);
+        Self::new(framebuffer.id(), size)
+    }
+
+    fn new(framebuffer: u32, size: (i32, i32)) -> Self {
+        Self {
+            framebuffer,
+            size,
+            depth: false,
+            blend: false,
+            cull: false,
+            clear_color: None,
+            saved: None,
+        }
+    }
+
+    /// Enables the depth test and the depth writes, and clears the depth
+    /// buffer, which needs a target with a depth buffer, like the window's.
+    pub fn with_depth(mut self) -> Self {
+        self.depth = true;
+        self
+    }
+
+    /// Enables alpha blending, which mixes the fragments by their alpha.
+    pub fn with_blend(mut self) -> Self {
+        self.blend = true;
+        self
+    }
+
+    /// Enables the culling of the back faces, which are the clockwise ones.
+    pub fn with_cull(mut self) -> Self {
+        self.cull = true;
+        self
+    }
+
+    /// Clears the color buffer to the given RGBA color.
+    pub fn with_clear(mut self, color: [f32; 4]) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    /// Saves the state that the pass changes, then binds the target, fits the
+    /// viewport to it, sets the capabilities and clears.
+    pub fn begin(mut self) -> Self {
+        let viewport = gl::get_integerv(gl::VIEWPORT, 4);
+        let mut clear_color = [0.0; 4];
+        gl::GetFloatv(gl::COLOR_CLEAR_VALUE, clear_color.as_mut_ptr());
+        self.saved = Some(Saved {
+            draw_framebuffer: gl::get_integer(gl::DRAW_FRAMEBUFFER_BINDING),
+            read_framebuffer: gl::get_integer(gl::READ_FRAMEBUFFER_BINDING),
+            viewport: [viewport[0], viewport[1], viewport[2], viewport[3]],
+            depth: gl::IsEnabled(gl::DEPTH_TEST) == gl::TRUE,
+            depth_mask: gl::get_integer(gl::DEPTH_WRITEMASK) != 0,
+            blend: gl::IsEnabled(gl::BLEND) == gl::TRUE,
+            cull: gl::IsEnabled(gl::CULL_FACE) == gl::TRUE,
+            blend_func: [
+                gl::get_integer(gl::BLEND_SRC_RGB),
+                gl::get_integer(gl::BLEND_DST_RGB),
+                gl::get_integer(gl::BLEND_SRC_ALPHA),
+                gl::get_integer(gl::BLEND_DST_ALPHA),
+            ],
+            clear_color,
+        });
+        gl::BindFramebuffer(gl::FRAMEBUFFER, self.framebuffer);
+        gl::Viewport(0, 0, self.size.0, self.size.1);
+        set(gl::DEPTH_TEST, self.depth);
+        set(gl::BLEND, self.blend);
+        set(gl::CULL_FACE, self.cull);
+        if self.blend {
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+        }
+        let mut mask = 0;
+        if let Some([r, g, b, a]) = self.clear_color {
+            gl::ClearColor(r, g, b, a);
+            mask |= gl::COLOR_BUFFER_BIT;
+        }
+        if self.depth {
+            gl::DepthMask(gl::TRUE);
+            mask |= gl::DEPTH_BUFFER_BIT;
+        }
+        if mask != 0 {
+            gl::Clear(mask);
+        }
+        self
+    }
+
+    /// Restores the state that was saved when the pass began.
+    /// Does nothing if the pass did not begin.
+    pub fn end(self) {
+        let Some(saved) = self.saved else {
+            return;
+        };
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, saved.draw_framebuffer as u32);
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, saved.read_framebuffer as u32);
+        let [x, y, width, height] = saved.viewport;
+        gl::Viewport(x, y, width, height);
+        set(gl::DEPTH_TEST, saved.depth);
+        gl::DepthMask(saved.depth_mask as u8);
+        set(gl::BLEND, saved.blend);
+        set(gl::CULL_FACE, saved.cull);
+        let [src_rgb, dst_rgb, src_alpha, dst_alpha] = saved.blend_func.map(|f| f as u32);
+        gl::BlendFuncSeparate(src_rgb, dst_rgb, src_alpha, dst_alpha);
+        let [r, g, b, a] = saved.clear_color;
+        gl::ClearColor(r, g, b, a);
+    }
+}
+
+/// Enables or disables the capability.
+fn set(capability: u32, on: bool) {
+    if on {
+        gl::Enable(capability);
+    } else {
+        gl::Disable(capability);
+    }
+}
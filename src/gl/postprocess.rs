@@ -0,0 +1,191 @@
+use crate::gl::{
+    blit::{draw_fullscreen, FULLSCREEN_VERTEX_SHADER},
+    buffer::Vao,
+    shader::Program,
+    texture::Texture2D,
+};
+
+const FXAA_SHADER: &str = r"#version 460 core
+in vec2 uv;
+uniform sampler2D image;
+uniform float edge_threshold;
+uniform float edge_threshold_min;
+uniform float subpixel;
+uniform int search_steps;
+out vec4 color;
+
+float luma(vec2 at) {
+    return dot(texture(image, at).rgb, vec3(0.299, 0.587, 0.114));
+}
+
+float luma_offset(ivec2 offset) {
+    return dot(textureOffset(image, uv, offset).rgb, vec3(0.299, 0.587, 0.114));
+}
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(image, 0));
+    vec4 center = texture(image, uv);
+    float m = dot(center.rgb, vec3(0.299, 0.587, 0.114));
+    float n = luma_offset(ivec2(0, 1));
+    float s = luma_offset(ivec2(0, -1));
+    float e = luma_offset(ivec2(1, 0));
+    float w = luma_offset(ivec2(-1, 0));
+    float high = max(max(max(n, s), max(e, w)), m);
+    float low = min(min(min(n, s), min(e, w)), m);
+    float range = high - low;
+    if (range < max(edge_threshold_min, high * edge_threshold)) {
+        color = center;
+        return;
+    }
+    float ne = luma_offset(ivec2(1, 1));
+    float nw = luma_offset(ivec2(-1, 1));
+    float se = luma_offset(ivec2(1, -1));
+    float sw = luma_offset(ivec2(-1, -1));
+
+    float average = (2.0 * (n + s + e + w) + ne + nw + se + sw) / 12.0;
+    float blend = smoothstep(0.0, 1.0, clamp(abs(average - m) / range, 0.0, 1.0));
+    blend = blend * blend * subpixel;
+
+    float horizontal = 2.0 * abs(n + s - 2.0 * m) + abs(ne + se - 2.0 * e) + abs(nw + sw - 2.0 * w);
+    float vertical = 2.0 * abs(e + w - 2.0 * m) + abs(ne + nw - 2.0 * n) + abs(se + sw - 2.0 * s);
+    bool is_horizontal = horizontal >= vertical;
+
+    float positive = is_horizontal ? n : e;
+    float negative = is_horizontal ? s : w;
+    float step_length = is_horizontal ? texel.y : texel.x;
+    float opposite = positive;
+    float gradient = abs(positive - m);
+    if (gradient < abs(negative - m)) {
+        step_length = -step_length;
+        opposite = negative;
+        gradient = abs(negative - m);
+    }
+
+    vec2 edge_uv = uv;
+    vec2 edge_step = is_horizontal ? vec2(texel.x, 0.0) : vec2(0.0, texel.y);
+    if (is_horizontal) {
+        edge_uv.y += step_length * 0.5;
+    } else {
+        edge_uv.x += step_length * 0.5;
+    }
+    float edge_luma = (m + opposite) * 0.5;
+    float threshold = gradient * 0.25;
+
+    vec2 p_uv = edge_uv + edge_step;
+    float p_delta = luma(p_uv) - edge_luma;
+    for (int i = 1; i < search_steps && abs(p_delta) < threshold; i++) {
+        p_uv += edge_step;
+        p_delta = luma(p_uv) - edge_luma;
+    }
+    vec2 n_uv = edge_uv - edge_step;
+    float n_delta = luma(n_uv) - edge_luma;
+    for (int i = 1; i < search_steps && abs(n_delta) < threshold; i++) {
+        n_uv -= edge_step;
+        n_delta = luma(n_uv) - edge_luma;
+    }
+
+    float p_distance = is_horizontal ? p_uv.x - uv.x : p_uv.y - uv.y;
+    float n_distance = is_horizontal ? uv.x - n_uv.x : uv.y - n_uv.y;
+    float edge_distance = min(p_distance, n_distance);
+    float delta = p_distance <= n_distance ? p_delta : n_delta;
+    float edge_blend = 0.0;
+    if ((delta >= 0.0) != (m - edge_luma >= 0.0)) {
+        edge_blend = 0.5 - edge_distance / (p_distance + n_distance);
+    }
+
+    vec2 final_uv = uv;
+    float final_blend = max(blend, edge_blend);
+    if (is_horizontal) {
+        final_uv.y += step_length * final_blend;
+    } else {
+        final_uv.x += step_length * final_blend;
+    }
+    color = vec4(texture(image, final_uv).rgb, center.a);
+}
+";
+
+/// Trade-offs between the cost and the smoothness of the [Fxaa].
+/// Defaults to [Medium](Self::Medium).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FxaaQuality {
+    /// Only the strong edges, with short searches along them.
+    Low,
+    /// Most of the edges, which is a good default.
+    #[default]
+    Medium,
+    /// Even the faint edges, with long searches and the full subpixel
+    /// smoothing, which blurs the textures a little.
+    High,
+}
+
+impl FxaaQuality {
+    /// Returns the relative and the minimum contrast of the edges, the amount
+    /// of subpixel smoothing and the steps of the searches along the edges.
+    fn settings(self) -> (f32, f32, f32, i32) {
+        match self {
+            FxaaQuality::Low => (0.25, 0.0833, 0.5, 4),
+            FxaaQuality::Medium => (0.166, 0.0625, 0.75, 8),
+            FxaaQuality::High => (0.125, 0.0312, 1.0, 12),
+        }
+    }
+}
+
+/// Fast approximate anti-aliasing, which smooths the edges of a rendered color
+/// texture by its contrast in a single fullscreen pass.
+/// Much cheaper than the [msaa](crate::Options::msaa), and works with any
+/// render target, like a [Framebuffer](crate::gl::framebuffer::Framebuffer)
+/// whose samples would need a resolve; in exchange, it cannot recover the
+/// details that are smaller than a pixel, and softens the texts a little.
+/// Manages its own program and empty vertex array.
+pub struct Fxaa {
+    program: Program,
+    vao: Vao,
+    quality: FxaaQuality,
+}
+
+impl Fxaa {
+    /// Creates the effect with the given quality.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot build the internal shader.
+    pub fn new(quality: FxaaQuality) -> Self {
+        let program = Program::new(FULLSCREEN_VERTEX_SHADER, FXAA_SHADER)
+            .expect("Could not build the FXAA shader!");
+        let mut fxaa = Self {
+            program,
+            vao: Vao::new(),
+            quality,
+        };
+        fxaa.set_quality(quality);
+        fxaa
+    }
+
+    /// Returns the quality.
+    pub fn quality(&self) -> FxaaQuality {
+        self.quality
+    }
+
+    /// Sets the quality.
+    pub fn set_quality(&mut self, quality: FxaaQuality) {
+        self.quality = quality;
+        let (edge_threshold, edge_threshold_min, subpixel, search_steps) = quality.settings();
+        self.program.set_i32("image", 0);
+        self.program.set_f32("edge_threshold", edge_threshold);
+        self.program
+            .set_f32("edge_threshold_min", edge_threshold_min);
+        self.program.set_f32("subpixel", subpixel);
+        self.program.set_i32("search_steps", search_steps);
+    }
+
+    /// Draws the anti-aliased color texture across the whole viewport of the
+    /// bound framebuffer, like [blit_texture](crate::gl::blit_texture), which
+    /// is usually the window's after rendering the scene to a framebuffer of
+    /// the same size. The texture must not be attached to the bound
+    /// framebuffer. Keeps the bound program, vertex array, active texture
+    /// unit and the texture on unit 0 as they were. Depth testing and
+    /// blending still apply; thus, consider disabling them before.
+    pub fn apply(&self, color: &Texture2D) {
+        draw_fullscreen(&self.program, &self.vao, color);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::gl;
+
+/// Measures how long the GPU takes to execute the commands between a
+/// [begin](Self::begin) and an [end](Self::end). Deleted when dropped.
+///
+/// The result is ready only after the GPU finishes the commands, which is
+/// usually a frame later. Reading it right after the end stalls until the GPU
+/// catches up; thus, prefer [try_get_result](Self::try_get_result) in the next
+/// frame, and use two queries in turns for measuring every frame.
+pub struct TimerQuery {
+    id: u32,
+    begun: bool,
+}
+
+impl TimerQuery {
+    /// Generates a new timer query.
+    pub fn new() -> Self {
+        let mut id = 0;
+        gl::GenQueries(1, &mut id);
+        Self { id, begun: false }
+    }
+
+    /// Returns the OpenGL name of the query.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Starts measuring. Only a single timer query can be measuring at a time.
+    pub fn begin(&mut self) {
+        gl::BeginQuery(gl::TIME_ELAPSED, self.id);
+        self.begun = true;
+    }
+
+    /// Stops measuring.
+    pub fn end(&mut self) {
+        gl::EndQuery(gl::TIME_ELAPSED);
+    }
+
+    /// Returns the measured milliseconds if the result is ready, without
+    /// waiting. Returns `None` if the query was never begun.
+    pub fn try_get_result(&self) -> Option<f64> {
+        if !self.begun {
+            return None;
+        }
+        let mut available = 0;
+        gl::GetQueryObjectiv(self.id, gl::QUERY_RESULT_AVAILABLE, &mut available);
+        (available == gl::TRUE as i32).then(|| self.get_result_ms())
+    }
+
+    /// Returns the measured milliseconds, waiting for the GPU to finish the
+    /// measured commands if needed. Returns 0 if the query was never begun.
+    pub fn get_result_ms(&self) -> f64 {
+        if !self.begun {
+            return 0.0;
+        }
+        let mut nanoseconds = 0;
+        gl::GetQueryObjectui64v(self.id, gl::QUERY_RESULT, &mut nanoseconds);
+        nanoseconds as f64 / 1e6
+    }
+}
+
+impl Default for TimerQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TimerQuery {
+    fn drop(&mut self) {
+        gl::DeleteQueries(1, &self.id);
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    ffi::CString,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::gl;
+
+/// Compiles a shader of the given kind, like `gl::VERTEX_SHADER`.
+/// Returns the OpenGL name of the shader, or the info log if compilation fails.
+pub fn compile(kind: u32, source: &str) -> Result<u32, String> {
+    let id = gl::CreateShader(kind);
+    let ptr = source.as_ptr() as *const gl::GLchar;
+    let len = source.len() as i32;
+    gl::ShaderSource(id, 1, &ptr, &len);
+    gl::CompileShader(id);
+    let mut status = 0;
+    gl::GetShaderiv(id, gl::COMPILE_STATUS, &mut status);
+    if status == gl::TRUE as i32 {
+        return Ok(id);
+    }
+    let mut len = 0;
+    gl::GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut len);
+    let mut log = vec![0u8; len.max(1) as usize];
+    gl::GetShaderInfoLog(id, len, &mut len, log.as_mut_ptr() as *mut gl::GLchar);
+    gl::DeleteShader(id);
+    log.truncate(len.max(0) as usize);
+    Err(String::from_utf8_lossy(&log).into_owned())
+}
+
+/// Links the given shaders into a program. The shaders are not deleted.
+/// Returns the OpenGL name of the program, or the info log if linking fails.
+pub fn link(shaders: &[u32]) -> Result<u32, String> {
+    let id = gl::CreateProgram();
+    for &shader in shaders {
+        gl::AttachShader(id, shader);
+    }
+    gl::LinkProgram(id);
+    for &shader in shaders {
+        gl::DetachShader(id, shader);
+    }
+    let mut status = 0;
+    gl::GetProgramiv(id, gl::LINK_STATUS, &mut status);
+    if status == gl::TRUE as i32 {
+        return Ok(id);
+    }
+    let mut len = 0;
+    gl::GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut len);
+    let mut log = vec![0u8; len.max(1) as usize];
+    gl::GetProgramInfoLog(id, len, &mut len, log.as_mut_ptr() as *mut gl::GLchar);
+    gl::DeleteProgram(id);
+    log.truncate(len.max(0) as usize);
+    Err(String::from_utf8_lossy(&log).into_owned())
+}
+
+/// Compiles the given sources and links them. Shaders are deleted afterwards.
+pub(crate) fn build(sources: &[(u32, &str)]) -> Result<u32, String> {
+    let mut shaders = Vec::with_capacity(sources.len());
+    for &(kind, source) in sources {
+        match compile(kind, source) {
+            Ok(shader) => shaders.push(shader),
+            Err(log) => {
+                shaders.iter().for_each(|&shader| gl::DeleteShader(shader));
+                return Err(log);
+            }
+        }
+    }
+    let program = link(&shaders);
+    shaders.iter().for_each(|&shader| gl::DeleteShader(shader));
+    program
+}
+
+/// Compiles a compute shader and links it into a program, which can be run
+/// with [Display::dispatch_compute](crate::Display::dispatch_compute).
+/// Returns the OpenGL name of the program, or the info log of the failing
+/// step otherwise.
+/// The buffers and the images that the shader reads and writes, like the
+/// [shader storage buffers](crate::gl::buffer), must be bound separately.
+pub fn compute(source: &str) -> Result<u32, String> {
+    build(&[(gl::COMPUTE_SHADER, source)])
+}
+
+/// Linked shader program. Deleted when dropped.
+pub struct Program {
+    id: u32,
+}
+
+impl Program {
+    /// Compiles and links the given vertex and fragment shader sources.
+    /// Returns the info log of the failing step otherwise.
+    pub fn new(vertex: &str, fragment: &str) -> Result<Self, String> {
+        build(&[(gl::VERTEX_SHADER, vertex), (gl::FRAGMENT_SHADER, fragment)]).map(|id| Self { id })
+    }
+
+    /// Returns the OpenGL name of the program.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Uses the program for the following draw calls.
+    pub fn bind(&self) {
+        gl::UseProgram(self.id);
+    }
+
+    /// Returns the location of the uniform with the given name, which is `-1`
+    /// if there is no such active uniform.
+    pub fn uniform_location(&self, name: &str) -> i32 {
+        match CString::new(name) {
+            Ok(name) => gl::GetUniformLocation(self.id, name.as_ptr()),
+            Err(_) => -1,
+        }
+    }
+
+    /// Returns the index of the uniform block with the given name, or `None`
+    /// if there is no such active block.
+    pub fn uniform_block_index(&self, name: &str) -> Option<u32> {
+        let name = CString::new(name).ok()?;
+        match gl::GetUniformBlockIndex(self.id, name.as_ptr()) {
+            gl::INVALID_INDEX => None,
+            index => Some(index),
+        }
+    }
+
+    /// Assigns the uniform block with the given name to the binding point,
+    /// which a [Ubo](crate::gl::buffer::Ubo) is
+    /// [bound](crate::gl::buffer::Ubo::bind_base) to.
+    /// Not needed for the blocks that declare their `layout(binding = N)`,
+    /// unless overriding it. Does nothing if there is no such active block.
+    pub fn bind_uniform_block(&self, name: &str, binding: u32) {
+        if let Some(index) = self.uniform_block_index(name) {
+            gl::UniformBlockBinding(self.id, index, binding);
+        }
+    }
+
+    /// Sets the `int` or `sampler` uniform with the given name.
+    /// Does not need the program to be bound.
+    pub fn set_i32(&self, name: &str, value: i32) {
+        gl::ProgramUniform1i(self.id, self.uniform_location(name), value);
+    }
+
+    /// Sets the `float` uniform with the given name.
+    /// Does not need the program to be bound.
+    pub fn set_f32(&self, name: &str, value: f32) {
+        gl::ProgramUniform1f(self.id, self.uniform_location(name), value);
+    }
+
+    /// Sets the `vec2` uniform with the given name.
+    /// Does not need the program to be bound.
+    pub fn set_vec2(&self, name: &str, value: [f32; 2]) {
+        gl::ProgramUniform2f(self.id, self.uniform_location(name), value[0], value[1]);
+    }
+
+    /// Sets the `vec4` uniform with the given name.
+    /// Does not need the program to be bound.
+    pub fn set_vec4(&self, name: &str, value: [f32; 4]) {
+        let [x, y, z, w] = value;
+        gl::ProgramUniform4f(self.id, self.uniform_location(name), x, y, z, w);
+    }
+
+    /// Sets the `mat4` uniform with the given name from a column-major matrix.
+    /// Does not need the program to be bound.
+    pub fn set_mat4(&self, name: &str, value: &[f32; 16]) {
+        gl::ProgramUniformMatrix4fv(
+            self.id,
+            self.uniform_location(name),
+            1,
+            gl::FALSE,
+            value.as_ptr(),
+        );
+    }
+}
+
+impl Drop for Program {
+    fn drop(&mut self) {
+        gl::DeleteProgram(self.id);
+    }
+}
+
+/// [Program] that is built from source files, and can be rebuilt from them
+/// while the application is running.
+pub struct HotProgram {
+    vertex_path: PathBuf,
+    fragment_path: PathBuf,
+    modified: Option<SystemTime>,
+    program: Program,
+}
+
+impl HotProgram {
+    /// Reads, compiles and links the given vertex and fragment shader files.
+    /// Returns the read error or the info log of the failing step otherwise.
+    pub fn new<P: AsRef<Path>, Q: AsRef<Path>>(
+        vertex_path: P,
+        fragment_path: Q,
+    ) -> Result<Self, String> {
+        let vertex_path = vertex_path.as_ref().to_path_buf();
+        let fragment_path = fragment_path.as_ref().to_path_buf();
+        let modified = Self::modified(&vertex_path, &fragment_path);
+        let program = Self::load(&vertex_path, &fragment_path)?;
+        Ok(Self {
+            vertex_path,
+            fragment_path,
+            modified,
+            program,
+        })
+    }
+
+    /// Returns the last program that was built successfully.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Rebuilds the program from the files.
+    /// Keeps the old program if reading, compiling or linking fails, and
+    /// returns the error.
+    pub fn reload(&mut self) -> Result<(), String> {
+        self.modified = Self::modified(&self.vertex_path, &self.fragment_path);
+        self.program = Self::load(&self.vertex_path, &self.fragment_path)?;
+        Ok(())
+    }
+
+    /// [Reloads](Self::reload) the program if any of the files were modified
+    /// since the last try, which is checked by their modification times.
+    /// Returns whether the program was rebuilt.
+    /// Cheap enough to call every frame, but a failing build is not retried
+    /// until the files change again.
+    pub fn reload_if_changed(&mut self) -> Result<bool, String> {
+        if Self::modified(&self.vertex_path, &self.fragment_path) == self.modified {
+            return Ok(false);
+        }
+        self.reload().map(|_| true)
+    }
+
+    fn modified(vertex_path: &Path, fragment_path: &Path) -> Option<SystemTime> {
+        let modified = |path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        modified(vertex_path).max(modified(fragment_path))
+    }
+
+    fn load(vertex_path: &Path, fragment_path: &Path) -> Result<Program, String> {
+        let read = |path: &Path| {
+            fs::read_to_string(path)
+                .map_err(|e| format!("Could not read `{}`: {}", path.display(), e))
+        };
+        Program::new(&read(vertex_path)?, &read(fragment_path)?)
+    }
+}
@@ -0,0 +1,162 @@
+use std::{mem, os::raw::c_void};
+
+use crate::gl::{self, buffer::Vao, shader::Program, texture::Texture2D};
+
+const VERTEX_SHADER: &str = r"#version 460 core
+layout(location = 0) in vec2 position;
+layout(location = 1) in vec2 uv;
+layout(location = 2) in vec4 color;
+uniform mat4 projection;
+out vec2 frag_uv;
+out vec4 frag_color;
+void main() {
+    gl_Position = projection * vec4(position, 0.0, 1.0);
+    frag_uv = uv;
+    frag_color = color;
+}
+";
+
+const FRAGMENT_SHADER: &str = r"#version 460 core
+in vec2 frag_uv;
+in vec4 frag_color;
+uniform sampler2D sprite;
+out vec4 color;
+void main() {
+    color = texture(sprite, frag_uv) * frag_color;
+}
+";
+
+/// Position, texture coordinates and color, in this order.
+const FLOATS_PER_VERTEX: usize = 8;
+
+/// Batches textured and tinted quads, and draws the consecutive ones with the
+/// same texture in a single draw call. Sprites from the same texture atlas are
+/// thus drawn together, while switching the texture flushes the batch.
+/// Manages its own vertex array, vertex buffer and shader.
+///
+/// Does not change the blending; enable [Capability::Blend](crate::state::Capability)
+/// with a blend function for transparent sprites.
+pub struct SpriteBatch {
+    program: Program,
+    vao: Vao,
+    vbo: u32,
+    vertices: Vec<f32>,
+    texture: Option<u32>,
+    projection: [f32; 16],
+}
+
+impl SpriteBatch {
+    /// Creates an empty batch with its own buffers and shader.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot build the internal shader.
+    pub fn new() -> Self {
+        let program = Program::new(VERTEX_SHADER, FRAGMENT_SHADER)
+            .expect("Could not build the sprite shader!");
+        let vao = Vao::new();
+        vao.bind();
+        let mut vbo = 0;
+        gl::GenBuffers(1, &mut vbo);
+        gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+        let stride = (FLOATS_PER_VERTEX * mem::size_of::<f32>()) as i32;
+        for (index, (size, offset)) in [(2, 0), (2, 2), (4, 4)].into_iter().enumerate() {
+            let offset = offset * mem::size_of::<f32>();
+            gl::EnableVertexAttribArray(index as u32);
+            gl::VertexAttribPointer(
+                index as u32,
+                size,
+                gl::FLOAT,
+                gl::FALSE,
+                stride,
+                offset as *const c_void,
+            );
+        }
+        gl::BindVertexArray(0);
+        Self {
+            program,
+            vao,
+            vbo,
+            vertices: Vec::new(),
+            texture: None,
+            projection: [0.0; 16],
+        }
+    }
+
+    /// Starts a batch with the given column-major projection matrix, such as
+    /// the one from `Display::pixel_perfect_ortho` with the `math` feature.
+    /// Discards what was drawn without an [end](Self::end).
+    pub fn begin(&mut self, projection: &[f32; 16]) {
+        self.vertices.clear();
+        self.texture = None;
+        self.projection = *projection;
+    }
+
+    /// Adds a sprite covering the `[x, y, width, height]` rectangle, showing the
+    /// `[u0, v0, u1, v1]` part of the texture tinted by the RGBA color.
+    /// Flushes the sprites before if the texture is different than theirs.
+    /// The texture must live until the batch is flushed, which is at the
+    /// latest at the [end](Self::end).
+    pub fn draw(&mut self, texture: &Texture2D, rect: [f32; 4], uv: [f32; 4], color: [f32; 4]) {
+        if self.texture != Some(texture.id()) {
+            self.flush();
+            self.texture = Some(texture.id());
+        }
+        let [x, y, width, height] = rect;
+        let [u0, v0, u1, v1] = uv;
+        let [r, g, b, a] = color;
+        let (left, top, right, bottom) = (x, y, x + width, y + height);
+        for (x, y, u, v) in [
+            (left, top, u0, v0),
+            (left, bottom, u0, v1),
+            (right, bottom, u1, v1),
+            (left, top, u0, v0),
+            (right, bottom, u1, v1),
+            (right, top, u1, v0),
+        ] {
+            self.vertices.extend_from_slice(&[x, y, u, v, r, g, b, a]);
+        }
+    }
+
+    /// Draws the remaining sprites of the batch.
+    pub fn end(&mut self) {
+        self.flush();
+        self.texture = None;
+    }
+
+    /// Draws the batched sprites in a single call and empties the batch.
+    /// Uses texture unit 0.
+    fn flush(&mut self) {
+        let texture = match self.texture {
+            Some(texture) if !self.vertices.is_empty() => texture,
+            _ => return,
+        };
+        self.program.bind();
+        self.program.set_mat4("projection", &self.projection);
+        self.program.set_i32("sprite", 0);
+        gl::ActiveTexture(gl::TEXTURE0);
+        gl::BindTexture(gl::TEXTURE_2D, texture);
+        gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+        gl::BufferData(
+            gl::ARRAY_BUFFER,
+            mem::size_of_val(self.vertices.as_slice()) as isize,
+            self.vertices.as_ptr() as *const c_void,
+            gl::STREAM_DRAW,
+        );
+        let count = (self.vertices.len() / FLOATS_PER_VERTEX) as i32;
+        self.vao.draw_arrays(gl::TRIANGLES, 0, count);
+        self.vertices.clear();
+    }
+}
+
+impl Default for SpriteBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SpriteBatch {
+    fn drop(&mut self) {
+        gl::DeleteBuffers(1, &self.vbo);
+    }
+}
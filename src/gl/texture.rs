@@ -0,0 +1,386 @@
+use std::{os::raw::c_void, ptr};
+
+use crate::gl;
+
+/// Returns whether the driver supports 2D textures of the given internal
+/// format, like `gl::RGBA16F` for the HDR render targets, which is better
+/// checked before creating a framebuffer than getting an incomplete one on
+/// the weak GPUs. Queried with `glGetInternalformativ`.
+pub fn is_format_supported(internal_format: u32) -> bool {
+    let mut supported = gl::FALSE as i32;
+    gl::GetInternalformativ(
+        gl::TEXTURE_2D,
+        internal_format,
+        gl::INTERNALFORMAT_SUPPORTED,
+        1,
+        &mut supported,
+    );
+    supported == gl::TRUE as i32
+}
+
+/// Errors of loading a [Texture2D] from a file.
+#[cfg(feature = "image")]
+#[derive(Debug)]
+pub enum TextureError {
+    /// Could not read the file.
+    Io(std::io::Error),
+    /// Could not decode the image, since the file is corrupt.
+    Decode(image::ImageError),
+    /// The format of the image, or its color type, is not supported by the
+    /// `image` crate.
+    Unsupported(image::error::UnsupportedError),
+    /// The image is larger than `GL_MAX_TEXTURE_SIZE` on either axis.
+    TooLarge {
+        /// Width of the image in pixels.
+        width: u32,
+        /// Height of the image in pixels.
+        height: u32,
+        /// Largest width and height of the textures.
+        max: u32,
+    },
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for TextureError {
+    fn from(error: image::ImageError) -> Self {
+        match error {
+            image::ImageError::IoError(error) => TextureError::Io(error),
+            image::ImageError::Unsupported(error) => TextureError::Unsupported(error),
+            error => TextureError::Decode(error),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl std::fmt::Display for TextureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TextureError::Io(error) => write!(f, "Could not read the image: {}", error),
+            TextureError::Decode(error) => write!(f, "Could not decode the image: {}", error),
+            TextureError::Unsupported(error) => write!(f, "Unsupported image: {}", error),
+            TextureError::TooLarge { width, height, max } => write!(
+                f,
+                "Image is {}x{}, which is larger than the maximum texture size {}!",
+                width, height, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl std::error::Error for TextureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TextureError::Io(error) => Some(error),
+            TextureError::Decode(error) => Some(error),
+            TextureError::Unsupported(error) => Some(error),
+            TextureError::TooLarge { .. } => None,
+        }
+    }
+}
+
+/// RGBA8 two dimensional texture with linear filtering and edge clamping.
+/// Deleted when dropped.
+pub struct Texture2D {
+    id: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Texture2D {
+    /// Creates a texture of the given size with undefined contents, which is
+    /// meant to be rendered to.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_pixels(width, height, ptr::null())
+    }
+
+    /// Creates a texture of the given size from tightly packed RGBA8 pixels,
+    /// starting from the bottom row.
+    ///
+    /// # Panics
+    ///
+    /// - If the amount of bytes does not match the size.
+    pub fn from_rgba(width: u32, height: u32, pixels: &[u8]) -> Self {
+        assert_eq!(
+            pixels.len(),
+            (width * height * 4) as usize,
+            "Pixels do not match the texture size!"
+        );
+        Self::with_pixels(width, height, pixels.as_ptr() as *const c_void)
+    }
+
+    /// Creates a texture from the image file at the given path, which is
+    /// decoded by the `image` crate in any of its formats, like PNG or JPEG,
+    /// and converted to RGBA8. The rows are flipped, since the images start
+    /// from the top row while OpenGL starts from the bottom one.
+    /// Needs the `image` feature.
+    #[cfg(feature = "image")]
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, TextureError> {
+        let image = image::open(path)?.flipv().into_rgba8();
+        let (width, height) = image.dimensions();
+        let max = gl::get_integer(gl::MAX_TEXTURE_SIZE).max(0) as u32;
+        if width > max || height > max {
+            return Err(TextureError::TooLarge { width, height, max });
+        }
+        Ok(Self::from_rgba(width, height, image.as_raw()))
+    }
+
+    fn with_pixels(width: u32, height: u32, pixels: *const c_void) -> Self {
+        let mut id = 0;
+        gl::GenTextures(1, &mut id);
+        gl::BindTexture(gl::TEXTURE_2D, id);
+        gl::TexImage2D(
+            gl::TEXTURE_2D,
+            0,
+            gl::RGBA8 as i32,
+            width as i32,
+            height as i32,
+            0,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            pixels,
+        );
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+        gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        Self { id, width, height }
+    }
+
+    /// Returns the OpenGL name of the texture.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Sets the anisotropic filtering level, which sharpens the texture when
+    /// it is seen at oblique angles, like on the floors. Clamped between 1,
+    /// which disables it, and the
+    /// [maximum](crate::Display::max_anisotropy) of the driver.
+    /// Consider 16 as the high quality setting, or 4 for the low end GPUs.
+    pub fn set_anisotropy(&self, level: f32) {
+        let max = gl::get_float(gl::MAX_TEXTURE_MAX_ANISOTROPY).max(1.0);
+        gl::TextureParameterf(self.id, gl::TEXTURE_MAX_ANISOTROPY, level.clamp(1.0, max));
+    }
+
+    /// Binds the texture to the given texture unit.
+    pub fn bind(&self, unit: u32) {
+        gl::ActiveTexture(gl::TEXTURE0 + unit);
+        gl::BindTexture(gl::TEXTURE_2D, self.id);
+    }
+}
+
+impl Drop for Texture2D {
+    fn drop(&mut self) {
+        gl::DeleteTextures(1, &self.id);
+    }
+}
+
+/// RGBA8 two dimensional texture array with linear filtering and edge
+/// clamping, which holds many same sized layers under a single binding.
+/// Sprites or tiles can be drawn from it without rebinding, by passing the
+/// layer to the shader's `sampler2DArray`. Deleted when dropped.
+pub struct TextureArray {
+    id: u32,
+    width: u32,
+    height: u32,
+    layers: u32,
+}
+
+impl TextureArray {
+    /// Creates a texture array with the given amount of layers of the given
+    /// size, with undefined contents.
+    pub fn new(width: u32, height: u32, layers: u32) -> Self {
+        let mut id = 0;
+        gl::GenTextures(1, &mut id);
+        gl::BindTexture(gl::TEXTURE_2D_ARRAY, id);
+        gl::TexImage3D(
+            gl::TEXTURE_2D_ARRAY,
+            0,
+            gl::RGBA8 as i32,
+            width as i32,
+            height as i32,
+            layers as i32,
+            0,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            ptr::null(),
+        );
+        gl::TexParameteri(
+            gl::TEXTURE_2D_ARRAY,
+            gl::TEXTURE_MIN_FILTER,
+            gl::LINEAR as i32,
+        );
+        gl::TexParameteri(
+            gl::TEXTURE_2D_ARRAY,
+            gl::TEXTURE_MAG_FILTER,
+            gl::LINEAR as i32,
+        );
+        gl::TexParameteri(
+            gl::TEXTURE_2D_ARRAY,
+            gl::TEXTURE_WRAP_S,
+            gl::CLAMP_TO_EDGE as i32,
+        );
+        gl::TexParameteri(
+            gl::TEXTURE_2D_ARRAY,
+            gl::TEXTURE_WRAP_T,
+            gl::CLAMP_TO_EDGE as i32,
+        );
+        Self {
+            id,
+            width,
+            height,
+            layers,
+        }
+    }
+
+    /// Returns the OpenGL name of the texture array.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the width of the layers in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the layers in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the amount of layers.
+    pub fn layers(&self) -> u32 {
+        self.layers
+    }
+
+    /// Uploads tightly packed RGBA8 pixels, starting from the bottom row, to
+    /// the given layer.
+    ///
+    /// # Panics
+    ///
+    /// - If the layer is out of bounds.
+    /// - If the amount of bytes does not match the layer size.
+    pub fn set_layer(&self, layer: u32, rgba: &[u8]) {
+        assert!(layer < self.layers, "Layer is out of bounds!");
+        assert_eq!(
+            rgba.len(),
+            (self.width * self.height * 4) as usize,
+            "Pixels do not match the layer size!"
+        );
+        gl::BindTexture(gl::TEXTURE_2D_ARRAY, self.id);
+        gl::TexSubImage3D(
+            gl::TEXTURE_2D_ARRAY,
+            0,
+            0,
+            0,
+            layer as i32,
+            self.width as i32,
+            self.height as i32,
+            1,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            rgba.as_ptr() as *const c_void,
+        );
+    }
+
+    /// Binds the texture array to the given texture unit.
+    pub fn bind(&self, unit: u32) {
+        gl::ActiveTexture(gl::TEXTURE0 + unit);
+        gl::BindTexture(gl::TEXTURE_2D_ARRAY, self.id);
+    }
+}
+
+impl Drop for TextureArray {
+    fn drop(&mut self) {
+        gl::DeleteTextures(1, &self.id);
+    }
+}
+
+/// RGBA8 cubemap texture with linear filtering and edge clamping, which is
+/// sampled by direction through the shader's `samplerCube`, like for the
+/// skyboxes and the reflection probes.
+/// Consider the [seamless filtering](crate::Display::set_seamless_cubemaps)
+/// across its faces. Deleted when dropped.
+pub struct Cubemap {
+    id: u32,
+    size: u32,
+}
+
+impl Cubemap {
+    /// Creates a cubemap from the tightly packed RGBA8 pixels of its six
+    /// square faces of the given size, in the order of `+X`, `-X`, `+Y`, `-Y`,
+    /// `+Z` and `-Z`. The faces start from the top row, like the image files,
+    /// since the cubemaps follow the RenderMan convention rather than the
+    /// bottom up one of the other textures.
+    ///
+    /// # Panics
+    ///
+    /// - If the amount of bytes of any face does not match the size.
+    pub fn from_faces(faces: [&[u8]; 6], size: u32) -> Self {
+        for face in faces {
+            assert_eq!(
+                face.len(),
+                (size * size * 4) as usize,
+                "Pixels do not match the face size!"
+            );
+        }
+        let mut id = 0;
+        gl::GenTextures(1, &mut id);
+        gl::BindTexture(gl::TEXTURE_CUBE_MAP, id);
+        for (i, face) in faces.iter().enumerate() {
+            gl::TexImage2D(
+                gl::TEXTURE_CUBE_MAP_POSITIVE_X + i as u32,
+                0,
+                gl::RGBA8 as i32,
+                size as i32,
+                size as i32,
+                0,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                face.as_ptr() as *const c_void,
+            );
+        }
+        for (pname, value) in [
+            (gl::TEXTURE_MIN_FILTER, gl::LINEAR),
+            (gl::TEXTURE_MAG_FILTER, gl::LINEAR),
+            (gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE),
+            (gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE),
+            (gl::TEXTURE_WRAP_R, gl::CLAMP_TO_EDGE),
+        ] {
+            gl::TexParameteri(gl::TEXTURE_CUBE_MAP, pname, value as i32);
+        }
+        Self { id, size }
+    }
+
+    /// Returns the OpenGL name of the cubemap.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the width and the height of the faces in pixels.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Binds the cubemap to the given texture unit.
+    pub fn bind(&self, unit: u32) {
+        gl::ActiveTexture(gl::TEXTURE0 + unit);
+        gl::BindTexture(gl::TEXTURE_CUBE_MAP, self.id);
+    }
+}
+
+impl Drop for Cubemap {
+    fn drop(&mut self) {
+        gl::DeleteTextures(1, &self.id);
+    }
+}
@@ -0,0 +1,12 @@
+use glfw::WindowEvent;
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+use crate::Display;
+
+/// Gives the platform window of the display, which is the same one of the
+/// underlying [glfw::Window].
+unsafe impl<T: FnMut(WindowEvent)> HasRawWindowHandle for Display<T> {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.window().raw_window_handle()
+    }
+}
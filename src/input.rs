@@ -0,0 +1,374 @@
+use std::{collections::HashSet, hash::Hash};
+
+use glfw::{Action, Key, MouseButton, WindowEvent};
+
+use crate::Display;
+
+/// Keyboard modifiers that are currently held, which are polled from the keys
+/// rather than tracked through the events. A modifier is held if either its
+/// left or its right key is down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Modifiers(glfw::Modifiers);
+
+impl Modifiers {
+    /// Returns whether any of the shift keys are held.
+    pub fn shift(&self) -> bool {
+        self.0.contains(glfw::Modifiers::Shift)
+    }
+
+    /// Returns whether any of the control keys are held.
+    pub fn ctrl(&self) -> bool {
+        self.0.contains(glfw::Modifiers::Control)
+    }
+
+    /// Returns whether any of the alt keys are held.
+    pub fn alt(&self) -> bool {
+        self.0.contains(glfw::Modifiers::Alt)
+    }
+
+    /// Returns whether any of the super keys, which are the Windows or the
+    /// Command keys, are held.
+    pub fn super_key(&self) -> bool {
+        self.0.contains(glfw::Modifiers::Super)
+    }
+
+    /// Returns the held modifiers in GLFW's representation, which is the same
+    /// as the one in the [key events](glfw::WindowEvent::Key).
+    /// Caps lock and num lock are never included.
+    pub fn glfw(&self) -> glfw::Modifiers {
+        self.0
+    }
+}
+
+/// Rebindable map of the keys to the application's actions, like `Jump` or
+/// `Fire` of an enum, with their state in the current frame.
+/// An action is down if any of its keys are held. The state is filled by
+/// [Display::update_input_map] once per frame, after the
+/// [update](Display::update).
+#[derive(Clone, Debug)]
+pub struct InputMap<A> {
+    bindings: Vec<(A, Key)>,
+    down: HashSet<A>,
+    previous: HashSet<A>,
+}
+
+impl<A: Copy + Eq + Hash> InputMap<A> {
+    /// Creates a map without any bindings.
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            down: HashSet::new(),
+            previous: HashSet::new(),
+        }
+    }
+
+    /// Binds the key to the action, which can have many keys, like both `W`
+    /// and `Up` for moving forward.
+    pub fn bind(&mut self, action: A, key: Key) -> &mut Self {
+        if !self.bindings.contains(&(action, key)) {
+            self.bindings.push((action, key));
+        }
+        self
+    }
+
+    /// Removes all the keys of the action, like before rebinding it.
+    pub fn unbind(&mut self, action: A) -> &mut Self {
+        self.bindings.retain(|&(bound, _)| bound != action);
+        self
+    }
+
+    /// Returns the keys that are bound to the action.
+    pub fn keys(&self, action: A) -> impl Iterator<Item = Key> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |&&(bound, _)| bound == action)
+            .map(|&(_, key)| key)
+    }
+
+    /// Returns whether any of the action's keys are held in this frame.
+    pub fn is_action_down(&self, action: A) -> bool {
+        self.down.contains(&action)
+    }
+
+    /// Returns whether the action went down in this frame.
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.down.contains(&action) && !self.previous.contains(&action)
+    }
+
+    /// Returns whether the action went up in this frame.
+    pub fn just_released(&self, action: A) -> bool {
+        !self.down.contains(&action) && self.previous.contains(&action)
+    }
+
+    /// Moves to the next frame with the given state of the keys.
+    fn fill(&mut self, is_key_down: impl Fn(Key) -> bool) {
+        std::mem::swap(&mut self.previous, &mut self.down);
+        self.down.clear();
+        for &(action, key) in &self.bindings {
+            if is_key_down(key) {
+                self.down.insert(action);
+            }
+        }
+    }
+}
+
+impl<A: Copy + Eq + Hash> Default for InputMap<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Detects the double clicks from the mouse button and cursor events.
+#[derive(Clone, Debug)]
+pub(crate) struct Clicks {
+    threshold: f64,
+    tolerance: f64,
+    cursor: (f64, f64),
+    last: Option<(MouseButton, f64, (f64, f64))>,
+    double_clicks: Vec<MouseButton>,
+}
+
+impl Clicks {
+    /// Creates a detector with the default threshold of 0.3 seconds and
+    /// tolerance of 4 screen coordinates.
+    pub(crate) fn new() -> Self {
+        Self {
+            threshold: 0.3,
+            tolerance: 4.0,
+            cursor: (0.0, 0.0),
+            last: None,
+            double_clicks: Vec::new(),
+        }
+    }
+
+    /// Records the event that happened at the given time.
+    /// A press of the same button soon enough and close enough to the last
+    /// one is a double click, which starts over; thus, a triple click is a
+    /// single double click.
+    pub(crate) fn record(&mut self, time: f64, event: &WindowEvent) {
+        match *event {
+            WindowEvent::CursorPos(x, y) => self.cursor = (x, y),
+            WindowEvent::MouseButton(button, Action::Press, _) => {
+                let (x, y) = self.cursor;
+                let double = self.last.is_some_and(|(last, at, (lx, ly))| {
+                    last == button
+                        && time - at <= self.threshold
+                        && (x - lx).abs() <= self.tolerance
+                        && (y - ly).abs() <= self.tolerance
+                });
+                if double {
+                    self.double_clicks.push(button);
+                    self.last = None;
+                } else {
+                    self.last = Some((button, time, self.cursor));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Virtual cursor that is confined to the window while the real one is
+/// disabled.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Capture {
+    raw: (f64, f64),
+    position: (f64, f64),
+}
+
+impl Capture {
+    /// Moves the virtual cursor by the movement of the real one, keeping it
+    /// in the window of the given size.
+    pub(crate) fn moved(&mut self, x: f64, y: f64, (width, height): (i32, i32)) {
+        let (dx, dy) = (x - self.raw.0, y - self.raw.1);
+        self.raw = (x, y);
+        self.position = (
+            (self.position.0 + dx).clamp(0.0, width.max(0) as f64),
+            (self.position.1 + dy).clamp(0.0, height.max(0) as f64),
+        );
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Confines the cursor to the window, or releases it.
+    /// The real cursor is [disabled](glfw::CursorMode::Disabled) while
+    /// captured; instead, a virtual cursor follows its movements but stops at
+    /// the window edges, which can be drawn by the application at the
+    /// [virtual cursor position](Self::virtual_cursor_pos).
+    /// Meant for dragging the sliders that should not escape the window.
+    /// When released, the real cursor appears where the virtual one was.
+    /// The [cursor events](glfw::WindowEvent::CursorPos) still carry the
+    /// positions of the real cursor.
+    pub fn set_cursor_captured(&mut self, on: bool) {
+        match (on, self.capture) {
+            (true, None) => {
+                let (x, y) = self.window.get_cursor_pos();
+                let (width, height) = self.window.get_size();
+                self.window.set_cursor_mode(glfw::CursorMode::Disabled);
+                self.capture = Some(Capture {
+                    raw: self.window.get_cursor_pos(),
+                    position: (
+                        x.clamp(0.0, width.max(0) as f64),
+                        y.clamp(0.0, height.max(0) as f64),
+                    ),
+                });
+            }
+            (false, Some(capture)) => {
+                self.window.set_cursor_mode(glfw::CursorMode::Normal);
+                let (x, y) = capture.position;
+                self.window.set_cursor_pos(x, y);
+                self.capture = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves the cursor to the given position in screen coordinates relative
+    /// to the window's content area, like for recentering custom camera
+    /// controls or warping the cursor in a UI. Only works while the window is
+    /// focused. Moves the [virtual cursor](Self::virtual_cursor_pos) instead,
+    /// inside the window, while the cursor is captured.
+    pub fn set_cursor_pos(&mut self, x: f64, y: f64) {
+        match &mut self.capture {
+            Some(capture) => {
+                let (width, height) = self.window.get_size();
+                capture.position = (
+                    x.clamp(0.0, width.max(0) as f64),
+                    y.clamp(0.0, height.max(0) as f64),
+                );
+            }
+            None => self.window.set_cursor_pos(x, y),
+        }
+    }
+
+    /// Returns whether the cursor is [captured](Self::set_cursor_captured).
+    pub fn is_cursor_captured(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    /// Returns the position of the virtual cursor in screen coordinates
+    /// relative to the window's content area, while the cursor is
+    /// [captured](Self::set_cursor_captured). Tracked from the cursor events
+    /// in [update](Self::update). Otherwise, the position of the real cursor.
+    pub fn virtual_cursor_pos(&self) -> (f64, f64) {
+        match self.capture {
+            Some(capture) => capture.position,
+            None => self.window.get_cursor_pos(),
+        }
+    }
+
+    /// Returns the mouse buttons that were double clicked since the last call,
+    /// in order. Detected from the [mouse button
+    /// events](glfw::WindowEvent::MouseButton) in [update](Self::update),
+    /// which are still passed to the handler.
+    pub fn take_double_clicks(&mut self) -> Vec<MouseButton> {
+        std::mem::take(&mut self.clicks.double_clicks)
+    }
+
+    /// Sets the maximum seconds between the presses of a double click, and
+    /// the maximum distance the cursor can move between them in screen
+    /// coordinates on both axes, which are 0.3 and 4 by default.
+    /// Consider the platform's settings, if available, for accessibility.
+    pub fn set_double_click(&mut self, threshold: f64, tolerance: f64) {
+        self.clicks.threshold = threshold;
+        self.clicks.tolerance = tolerance;
+    }
+
+    /// Returns whether the given key is currently held, which is the last
+    /// state reported by the platform until the latest poll.
+    pub fn is_key_down(&self, key: Key) -> bool {
+        self.window.get_key(key) != Action::Release
+    }
+
+    /// Fills the state of the map's actions for this frame from the
+    /// [held keys](Self::is_key_down). Call it once per frame after the
+    /// [update](Self::update); otherwise, the
+    /// [presses](InputMap::just_pressed) are seen for more than a frame or
+    /// missed.
+    pub fn update_input_map<A: Copy + Eq + Hash>(&self, map: &mut InputMap<A>) {
+        map.fill(|key| self.is_key_down(key));
+    }
+
+    /// Returns the platform specific scancode of the given key, which
+    /// identifies the physical key regardless of the keyboard layout, or -1
+    /// if the key has no scancode.
+    /// Store the scancodes for rebindable controls that should stay on the same
+    /// keys when the layout changes.
+    pub fn key_scancode(&self, key: Key) -> i32 {
+        glfw::get_key_scancode(Some(key)).unwrap_or(-1)
+    }
+
+    /// Returns the name of the physical key with the given scancode in the
+    /// current keyboard layout, like `"z"` for the same key on QWERTY and
+    /// `"y"` on QWERTZ, for showing the bindings.
+    /// Most of the non-printable keys have no names.
+    pub fn scancode_name(&self, scancode: i32) -> Option<String> {
+        glfw::get_key_name(None, Some(scancode))
+    }
+
+    /// Returns whether the physical key with the given scancode is held.
+    /// Tracked from the [key events](glfw::WindowEvent::Key) in
+    /// [update](Self::update), since GLFW can only poll the keys by [Key].
+    pub fn is_scancode_down(&self, scancode: i32) -> bool {
+        self.held_scancodes.contains(&scancode)
+    }
+
+    /// Returns the currently held [Modifiers].
+    /// For a save shortcut, check
+    /// `display.modifiers().ctrl() && display.is_key_down(Key::S)`.
+    pub fn modifiers(&self) -> Modifiers {
+        let mut modifiers = glfw::Modifiers::empty();
+        for (left, right, modifier) in [
+            (Key::LeftShift, Key::RightShift, glfw::Modifiers::Shift),
+            (
+                Key::LeftControl,
+                Key::RightControl,
+                glfw::Modifiers::Control,
+            ),
+            (Key::LeftAlt, Key::RightAlt, glfw::Modifiers::Alt),
+            (Key::LeftSuper, Key::RightSuper, glfw::Modifiers::Super),
+        ] {
+            if self.is_key_down(left) || self.is_key_down(right) {
+                modifiers |= modifier;
+            }
+        }
+        Modifiers(modifiers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glfw::Modifiers;
+
+    use super::*;
+
+    fn press(clicks: &mut Clicks, time: f64, button: MouseButton) {
+        let event = WindowEvent::MouseButton(button, Action::Press, Modifiers::empty());
+        clicks.record(time, &event);
+    }
+
+    #[test]
+    fn double_clicks_need_same_button_time_and_place() {
+        let mut clicks = Clicks::new();
+        press(&mut clicks, 1.0, MouseButton::Button1);
+        press(&mut clicks, 1.2, MouseButton::Button1);
+        press(&mut clicks, 1.3, MouseButton::Button1);
+        press(&mut clicks, 2.0, MouseButton::Button1);
+        press(&mut clicks, 2.1, MouseButton::Button2);
+        clicks.record(2.2, &WindowEvent::CursorPos(100.0, 0.0));
+        press(&mut clicks, 2.3, MouseButton::Button2);
+        assert_eq!(clicks.double_clicks, [MouseButton::Button1]);
+    }
+
+    #[test]
+    fn actions_follow_any_of_their_keys() {
+        let mut map = InputMap::new();
+        map.bind("jump", Key::Space).bind("jump", Key::W);
+        map.fill(|key| key == Key::W);
+        assert!(map.is_action_down("jump") && map.just_pressed("jump"));
+        map.fill(|key| key == Key::Space);
+        assert!(map.is_action_down("jump") && !map.just_pressed("jump"));
+        map.fill(|_| false);
+        assert!(map.just_released("jump"));
+    }
+}
@@ -5,26 +5,163 @@
 /// Only modification that was done on the output of glad was the removal of
 /// the unsafe keyword from the macro `func!` that defined the functions.
 /// Thus, all OpenGL calls can be tought of as unsafe!
+///
+/// Helper modules that are written by hand are declared at the top of the
+/// generated file and live in the `gl` directory.
 pub mod gl;
 
+/// Global GLFW callbacks, which are not tied to a [Display].
+pub mod callbacks;
+
+/// Monitor properties, like the DPI.
+pub mod monitor;
+
+/// Keyboard and mouse input helpers of the [Display].
+pub mod input;
+
+/// Typed OpenGL state helpers of the [Display].
+pub mod state;
+
+/// [raw_window_handle::HasRawWindowHandle] implementation.
+#[cfg(feature = "raw-window-handle")]
+pub mod handle;
+
+mod clock;
+
+/// Replaceable time sources of the [Display]'s clock.
+pub mod time;
+
+mod refresh;
+
+mod platform;
+
+mod window_set;
+
+mod context_group;
+
+mod render_scale;
+
+/// Worker threads that send draw commands to the [Display].
+pub mod worker;
+
 /// [min_timer::Now] implementation.
 #[cfg(feature = "min_timer")]
 pub mod now;
 
-use glfw::{Context, Glfw, Monitor, SwapInterval, Window, WindowEvent};
-use std::sync::mpsc::Receiver;
+pub use callbacks::set_joystick_callback;
+pub use context_group::ContextGroup;
+#[cfg(feature = "min_timer")]
+pub use min_timer::Sec;
+pub use render_scale::UpscaleFilter;
+pub use time::{timer_frequency, timer_value};
+pub use window_set::WindowSet;
+
+use clock::{Clock, FrameTimes};
+use glfw::{Action, Context, CursorMode, Glfw, Monitor, SwapInterval, Window, WindowEvent};
+use input::{Capture, Clicks};
+use monitor::GammaRestore;
+use platform::DamageSwap;
+use refresh::RefreshHook;
+use render_scale::RenderScale;
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    os::raw::c_void,
+    sync::mpsc::Receiver,
+    thread,
+    time::{Duration, Instant},
+};
+use time::{GlfwTime, TimeSource};
+
+/// Kind of the OpenGL context of a display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClientApi {
+    /// OpenGL 4.6 core profile, which is what the [gl] module is loaded for.
+    OpenGl,
+    /// OpenGL ES 3.0, which is not supported on macOS.
+    /// The [gl] module still targets desktop OpenGL; thus, only the functions
+    /// that are common with OpenGL ES can be called, and anything newer than
+    /// OpenGL ES 3.0 crashes. The debug output is not set up.
+    /// Mainly for context creation experiments.
+    OpenGlEs,
+}
+
+impl ClientApi {
+    /// Returns the requested context version as `(major, minor)`.
+    pub fn version(self) -> (u32, u32) {
+        match self {
+            ClientApi::OpenGl => (4, 6),
+            ClientApi::OpenGlEs => (3, 0),
+        }
+    }
+}
+
+/// Ways of waiting for the monitor refreshes between frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VsyncMode {
+    /// Does not wait, which can lead to tearing.
+    Off,
+    /// Waits for a refresh between frames.
+    On,
+    /// Waits for a refresh unless the frame is late, when it is shown right
+    /// away with tearing instead of waiting for the next refresh; thus, the
+    /// frame rate does not halve when it drops below the refresh rate.
+    /// Needs `EXT_swap_control_tear` support from the platform.
+    Adaptive,
+}
+
+/// Ways of pacing the frames of a display, which combine the swap interval
+/// with a frame rate cap that sleeps in the [swap](Display::swap).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FramePacing {
+    /// Waits for a monitor refresh between frames without a cap.
+    Vsync,
+    /// Neither waits nor caps, which renders as fast as possible.
+    Uncapped,
+    /// Caps the frame rate to the given one without waiting for the monitor
+    /// refreshes.
+    CapTo(f64),
+    /// Caps the frame rate to the refresh rate of the monitor the display is
+    /// on without waiting for the refreshes, which is the smooth pacing of
+    /// vsync without its input latency, but with tearing.
+    MatchRefresh,
+}
+
+/// Named sets of the quality related [Options], for the settings menus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QualityPreset {
+    /// No multisampling and no vsync, with the non-Retina framebuffer on
+    /// macOS, for the weak GPUs and the laptops on battery.
+    Low,
+    /// 4x multisampling with vsync.
+    Medium,
+    /// 8x multisampling with vsync, with the discrete GPU forced on the dual
+    /// GPU Macs.
+    High,
+}
 
 /// Options for creating a display.
 pub struct Options {
     /// With of the window in pixels.
     pub width: u32,
     /// Height of the window in pixels.
-    /// Consider the aspect ratio (width/height) as 16/9.
+    /// Any aspect ratio (width/height) works; the window is centered either
+    /// way.
     pub height: u32,
     /// Title of the window.
     pub title: String,
     /// Whether the window occupies all the monitor.
     pub fullscreen: bool,
+    /// Whether the window can be resized by the user by dragging its edges.
+    /// The viewport follows the framebuffer size if so.
+    /// Meaningfull when not in fullscreen mode.
+    /// Consider `false` for games, which are mostly designed for a fixed size.
+    pub resizable: bool,
+    /// Aspect ratio as `(width, height)` that the window keeps while it is
+    /// resized by the user, like `(16, 9)`.
+    /// Meaningfull when `resizable`.
+    /// Consider `None`, unless the rendering needs a fixed aspect ratio.
+    pub lock_aspect: Option<(u32, u32)>,
     /// Whether the window has frame.
     /// Meaningfull when not in fullscreen mode.
     pub decorated: bool,
@@ -38,61 +175,411 @@ pub struct Options {
     /// Can decrease the frame rate a lot when struggling around the refresh rate.
     /// If not setted the frame rate is unbounded, which can lead to tearing.
     pub vsync: bool,
+    /// Amount of monitor refreshes waited between frames, which overrides
+    /// `vsync` if set.
+    /// Consider `SwapInterval::Sync(2)` for half the refresh rate, or
+    /// `SwapInterval::Adaptive` for allowing tearing when a frame is late,
+    /// which needs `EXT_swap_control_tear` support.
+    /// Uses `vsync` if `None`.
+    pub swap_interval: Option<SwapInterval>,
+    /// Whether an OpenGL debug context is requested and its messages are
+    /// printed to the standard error.
+    /// Consider `cfg!(debug_assertions)` for getting them only in debug builds,
+    /// or `true` for collecting them from a release build as well.
+    pub debug_context: bool,
+    /// Whether the context is created with `KHR_no_error`, which disables all
+    /// the error checking for performance.
+    /// Then, `glGetError` and the debug output do not report anything, and any
+    /// incorrect OpenGL call is undefined behavior.
+    /// Creation fails if both this and `debug_context` are enabled.
+    /// Consider `false`, unless for the final builds.
+    pub no_error_context: bool,
+    /// Whether the cursor is moved to the center of the window on creation.
+    /// Consider `false` for tools, where the cursor should stay where it is.
+    pub center_cursor: bool,
+    /// Mode of the cursor on creation.
+    /// Consider `CursorMode::Disabled` for first person camera controls.
+    pub start_cursor_mode: CursorMode,
+    /// Refresh rate of the monitor in fullscreen mode in hertz.
+    /// Consider `Some(144)` for locking a high refresh rate mode.
+    /// Uses the highest available one if `None`.
+    pub refresh_rate: Option<u32>,
+    /// Kind of the OpenGL context, which is created by the native context API
+    /// of the platform.
+    /// Consider `ClientApi::OpenGl`, unless experimenting with OpenGL ES.
+    pub client_api: ClientApi,
+    /// Whether the framebuffer has the full resolution of the Retina
+    /// displays on macOS; otherwise, it has the size of the window in screen
+    /// coordinates, and is upscaled blurry. The
+    /// [pixel ratio](Display::logical_to_physical) follows either way.
+    /// Ignored on the other platforms.
+    /// Consider `true` for sharp rendering, or `false` for a quarter of the
+    /// fill cost.
+    pub retina_framebuffer: bool,
+    /// Whether the context lets macOS switch between the integrated and the
+    /// discrete GPUs on the dual GPU Macs, which saves battery; otherwise, the
+    /// discrete GPU is forced while the display is alive.
+    /// Ignored on the other platforms.
+    /// Consider `true`, unless needing the performance of the discrete GPU.
+    pub graphics_switching: bool,
+    /// Additional GLFW window hints, which are applied in order after the ones
+    /// that come from the other options; thus, they can override them.
+    /// Escape hatch for the hints that are not modeled here, like
+    /// `WindowHint::ScaleToMonitor(true)`. Conflicting hints can make the
+    /// window creation fail.
+    /// Consider `Vec::new()`, unless needing a specific hint.
+    pub extra_hints: Vec<glfw::WindowHint>,
+    /// Whether the viewport is set to the window size on creation.
+    /// Otherwise, no viewport state is touched on creation, which leaves the
+    /// OpenGL default that is the framebuffer size anyway.
+    /// Consider `true`, unless the viewports are fully managed by the
+    /// application from the first frame, like in multi-view setups.
+    pub set_initial_viewport: bool,
 }
 
 impl Options {
+    /// Returns the options of the given preset for a resizable, decorated
+    /// 1280x720 window without a title, with the OpenGL debug output in the
+    /// debug builds. Override the rest with the struct update syntax, like
+    /// `Options { title: "Game".into(), ..Options::preset(QualityPreset::High) }`.
+    pub fn preset(preset: QualityPreset) -> Options {
+        let (msaa, vsync, retina_framebuffer, graphics_switching) = match preset {
+            QualityPreset::Low => (None, false, false, true),
+            QualityPreset::Medium => (Some(4), true, true, true),
+            QualityPreset::High => (Some(8), true, true, false),
+        };
+        Options {
+            width: 1280,
+            height: 720,
+            title: String::new(),
+            fullscreen: false,
+            resizable: true,
+            lock_aspect: None,
+            decorated: true,
+            msaa,
+            vsync,
+            swap_interval: None,
+            debug_context: cfg!(debug_assertions),
+            no_error_context: false,
+            center_cursor: true,
+            start_cursor_mode: CursorMode::Normal,
+            refresh_rate: None,
+            client_api: ClientApi::OpenGl,
+            retina_framebuffer,
+            graphics_switching,
+            extra_hints: Vec::new(),
+            set_initial_viewport: true,
+        }
+    }
+
     fn config(&self, glfw: &mut Glfw) {
         use glfw::WindowHint::*;
         glfw.default_window_hints();
-        glfw.window_hint(Resizable(false));
+        glfw.window_hint(Resizable(self.resizable));
         glfw.window_hint(Decorated(self.decorated));
         glfw.window_hint(Samples(self.msaa));
-        glfw.window_hint(ContextVersion(4, 6));
-        glfw.window_hint(OpenGlForwardCompat(true));
-        glfw.window_hint(OpenGlProfile(glfw::OpenGlProfileHint::Core));
-        #[cfg(debug_assertions)]
-        glfw.window_hint(OpenGlDebugContext(true));
-    }
-
-    fn create(&self, glfw: &mut Glfw, monitor: &Monitor) -> (Window, Receiver<(f64, WindowEvent)>) {
-        let (mut window, events) = glfw
-            .create_window(
-                self.width,
-                self.height,
-                self.title.as_str(),
-                if self.fullscreen {
-                    glfw::WindowMode::FullScreen(monitor)
-                } else {
-                    glfw::WindowMode::Windowed
-                },
-            )
-            .expect("Could not create the window!");
-        let vidmode = monitor
-            .get_video_mode()
-            .expect("Could not get the vidmode of the monitor!");
-        window.set_pos(
-            (vidmode.width - self.width) as i32 / 2,
-            (vidmode.height - self.height) as i32 / 2,
-        );
-        window.set_cursor_pos(self.width as f64 / 2.0, self.height as f64 / 2.0);
-        (window, events)
+        glfw.window_hint(RefreshRate(self.refresh_rate));
+        glfw.window_hint(ContextCreationApi(glfw::ContextCreationApi::Native));
+        let (major, minor) = self.client_api.version();
+        glfw.window_hint(ContextVersion(major, minor));
+        match self.client_api {
+            crate::ClientApi::OpenGl => {
+                glfw.window_hint(ClientApi(glfw::ClientApiHint::OpenGl));
+                glfw.window_hint(OpenGlForwardCompat(true));
+                glfw.window_hint(OpenGlProfile(glfw::OpenGlProfileHint::Core));
+            }
+            crate::ClientApi::OpenGlEs => {
+                glfw.window_hint(ClientApi(glfw::ClientApiHint::OpenGlEs));
+            }
+        }
+        glfw.window_hint(OpenGlDebugContext(self.debug_context));
+        glfw.window_hint(ContextNoError(self.no_error_context));
+        glfw.window_hint(CocoaRetinaFramebuffer(self.retina_framebuffer));
+        glfw.window_hint(CocoaGraphicsSwitching(self.graphics_switching));
+        for hint in &self.extra_hints {
+            glfw.window_hint(hint.clone());
+        }
+    }
+
+    /// Creates the window on the given monitor, which shares the objects of
+    /// the given window's context if any. Returns `None` if cannot create it.
+    fn create(
+        &self,
+        glfw: &mut Glfw,
+        monitor: &Monitor,
+        share: Option<&Window>,
+    ) -> Option<(Window, Receiver<(f64, WindowEvent)>)> {
+        let mode = if self.fullscreen {
+            glfw::WindowMode::FullScreen(monitor)
+        } else {
+            glfw::WindowMode::Windowed
+        };
+        let (mut window, events) = match share {
+            Some(share) => share.create_shared(self.width, self.height, &self.title, mode),
+            None => glfw.create_window(self.width, self.height, &self.title, mode),
+        }?;
+        // The glfw crate does not destroy the windows created with a shared
+        // context when they are dropped, which would leave them on the screen
+        // with their callbacks sending to the freed event channel.
+        window.is_shared = false;
+        let (x, y, width, height) = if self.fullscreen {
+            let vidmode = monitor
+                .get_video_mode()
+                .expect("Could not get the vidmode of the monitor!");
+            (0, 0, vidmode.width as i32, vidmode.height as i32)
+        } else {
+            monitor.get_workarea()
+        };
+        window.set_pos(center(x, width, self.width), center(y, height, self.height));
+        if let (Some((numer, denom)), true) = (self.lock_aspect, self.resizable) {
+            window.set_aspect_ratio(numer, denom);
+        }
+        if self.center_cursor {
+            window.set_cursor_pos(self.width as f64 / 2.0, self.height as f64 / 2.0);
+        }
+        window.set_cursor_mode(self.start_cursor_mode);
+        Some((window, events))
+    }
+
+    /// Makes the context of the created window current, loads the OpenGL
+    /// functions and sets it up. Returns the core functions that could not be
+    /// loaded.
+    fn setup(&self, glfw: &mut Glfw, window: &mut Window) -> Vec<&'static str> {
+        window.set_all_polling(true);
+        window.make_current();
+        let mut unloaded_functions = Vec::new();
+        gl::load(|name| {
+            let proc = glfw.get_proc_address_raw(name);
+            if proc.is_null() && is_core_function(name) {
+                unloaded_functions.push(name);
+            }
+            proc
+        });
+        if self.client_api == ClientApi::OpenGl {
+            warn_unloaded(&unloaded_functions);
+        }
+        self.config_context(glfw);
+        unloaded_functions
+    }
+
+    /// Returns the `swap_interval`, or the one from `vsync` if not set.
+    fn swap_interval(&self) -> SwapInterval {
+        self.swap_interval
+            .unwrap_or(SwapInterval::Sync(self.vsync as u32))
     }
 
     fn config_context(&self, glfw: &mut Glfw) {
-        glfw.set_swap_interval(SwapInterval::Sync(self.vsync as u32));
-        gl::Viewport(0, 0, self.width as i32, self.height as i32);
+        glfw.set_swap_interval(self.swap_interval());
+        if self.set_initial_viewport {
+            gl::Viewport(0, 0, self.width as i32, self.height as i32);
+        }
+        if self.client_api == ClientApi::OpenGlEs {
+            return;
+        }
         match self.msaa {
             Some(_) => gl::Enable(gl::MULTISAMPLE),
             None => gl::Disable(gl::MULTISAMPLE),
         }
+        if self.debug_context {
+            gl::Enable(gl::DEBUG_OUTPUT);
+            gl::Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS);
+            gl::DebugMessageCallback(debug_callback, std::ptr::null());
+            #[cfg(not(feature = "log"))]
+            gl::DebugMessageControl(
+                gl::DONT_CARE,
+                gl::DONT_CARE,
+                gl::DEBUG_SEVERITY_NOTIFICATION,
+                0,
+                std::ptr::null(),
+                gl::FALSE,
+            );
+        }
+    }
+}
+
+/// Returns the start of a window of the given size that is centered in the
+/// given range. Windows that are larger than the range start at its start,
+/// which keeps their title bars reachable.
+fn center(start: i32, range: i32, size: u32) -> i32 {
+    start + (range - size as i32).max(0) / 2
+}
+
+/// Initializes GLFW for creating displays with [Display::new_with_glfw], with
+/// the same error handling as [Display::new].
+///
+/// # Panics
+///
+/// - On GLFW Errors, unless the `log` feature is enabled, which logs them.
+/// - If cannot initialize GLFW.
+pub fn init() -> Glfw {
+    #[cfg(feature = "log")]
+    let callback = Some(glfw::Callback {
+        f: log_glfw_error as fn(glfw::Error, String, &()),
+        data: (),
+    });
+    #[cfg(not(feature = "log"))]
+    let callback = glfw::FAIL_ON_ERRORS;
+    glfw::init(callback).expect("Could not initialize the GLFW!")
+}
+
+/// Logs the GLFW errors instead of panicking.
+#[cfg(feature = "log")]
+fn log_glfw_error(error: glfw::Error, description: String, _: &()) {
+    log::error!("GLFW error {:?}: {}", error, description);
+}
+
+/// Returns whether the OpenGL function with the given name is a core one,
+/// rather than an extension one with a vendor suffix like `ARB` or `NV`.
+/// Extension functions are expected to be missing on most drivers.
+fn is_core_function(name: &str) -> bool {
+    let suffix = name
+        .bytes()
+        .rev()
+        .take_while(u8::is_ascii_uppercase)
+        .count();
+    suffix < 2
+}
+
+/// Warns about the core OpenGL functions that could not be loaded, which
+/// would crash if called.
+fn warn_unloaded(unloaded: &[&'static str]) {
+    if unloaded.is_empty() {
+        return;
+    }
+    warn(&format!(
+        "Could not load the OpenGL functions: {}",
+        unloaded.join(", ")
+    ));
+}
+
+/// Logs the warning with the `log` feature, or prints it to the standard
+/// error otherwise.
+fn warn(message: &str) {
+    #[cfg(feature = "log")]
+    log::warn!("{}", message);
+    #[cfg(not(feature = "log"))]
+    eprintln!("{}", message);
+}
+
+/// Reports the messages of the OpenGL debug output.
+/// Logs them at the level that matches their severity with the `log` feature.
+/// Otherwise, prints them to the standard error, except the notifications.
+extern "system" fn debug_callback(
+    _source: gl::GLenum,
+    _type: gl::GLenum,
+    id: gl::GLuint,
+    severity: gl::GLenum,
+    length: gl::GLsizei,
+    message: *const gl::GLchar,
+    _user: *mut c_void,
+) {
+    let message = unsafe { std::slice::from_raw_parts(message as *const u8, length as usize) };
+    let message = String::from_utf8_lossy(message);
+    #[cfg(feature = "log")]
+    {
+        let level = match severity {
+            gl::DEBUG_SEVERITY_HIGH => log::Level::Error,
+            gl::DEBUG_SEVERITY_MEDIUM => log::Level::Warn,
+            gl::DEBUG_SEVERITY_LOW => log::Level::Info,
+            _ => log::Level::Debug,
+        };
+        log::log!(level, "OpenGL debug message {}: {}", id, message);
+    }
+    #[cfg(not(feature = "log"))]
+    {
+        let severity = match severity {
+            gl::DEBUG_SEVERITY_HIGH => "high",
+            gl::DEBUG_SEVERITY_MEDIUM => "medium",
+            gl::DEBUG_SEVERITY_LOW => "low",
+            _ => "notification",
+        };
+        eprintln!(
+            "OpenGL debug message {} with {} severity: {}",
+            id, severity, message
+        );
+    }
+}
+
+/// Returns whether the event comes from the user's input devices.
+fn is_input(event: &WindowEvent) -> bool {
+    matches!(
+        event,
+        WindowEvent::Key(..)
+            | WindowEvent::Char(_)
+            | WindowEvent::MouseButton(..)
+            | WindowEvent::CursorPos(..)
+            | WindowEvent::Scroll(..)
+    )
+}
+
+/// Errors of the [Display] operations that can fail without panicking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayError {
+    /// Could not find the monitor to create the window on.
+    NoMonitor,
+    /// Could not create the window or its context with the given options,
+    /// like an unsupported `client_api`.
+    WindowCreation,
+}
+
+impl std::fmt::Display for DisplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DisplayError::NoMonitor => write!(f, "Could not find the monitor!"),
+            DisplayError::WindowCreation => write!(f, "Could not create the window!"),
+        }
     }
 }
 
+impl std::error::Error for DisplayError {}
+
+/// Filter of the events before they are handled by the [Display].
+type EventFilter = dyn FnMut(WindowEvent) -> Option<WindowEvent>;
+
 /// [GLFW](glfw) window with valid OpenGL 4.6 CORE context loaded by [GLAD](gl).
 pub struct Display<T: FnMut(WindowEvent)> {
     window: Window,
     handler: T,
     events: Receiver<(f64, WindowEvent)>,
+    text_input: String,
+    frame_count: u64,
+    clock: Clock,
+    time_source: Box<dyn TimeSource>,
+    unloaded_functions: Vec<&'static str>,
+    cursor_inside: bool,
+    refresh: Option<RefreshHook<T>>,
+    focused: bool,
+    background_fps: Option<f64>,
+    last_swap: Instant,
+    clicks: Clicks,
+    held_scancodes: HashSet<glfw::Scancode>,
+    gamma: Option<GammaRestore>,
+    windowed_rect: Option<(i32, i32, i32, i32)>,
+    options: Options,
+    last_frame: f64,
+    delta: f64,
+    max_delta: Option<f64>,
+    capture: Option<Capture>,
+    adaptive_vsync: bool,
+    frame_times: FrameTimes,
+    iconified: bool,
+    skip_hidden: bool,
+    recorder: Option<Box<dyn FnMut(f64, WindowEvent)>>,
+    context_lost: Cell<bool>,
+    max_events: Option<usize>,
+    frame_cap: Option<f64>,
+    damage_swap: Option<DamageSwap>,
+    monitor_changes: Option<u64>,
+    ignore_key_repeat: bool,
+    swap_group: u32,
+    focus_on_hover: bool,
+    hover_focus_requested: bool,
+    workers: Vec<Receiver<worker::DrawCommand>>,
+    render_scale: RenderScale,
+    event_filter: Option<Box<EventFilter>>,
+    measure_latency: bool,
+    pending_input: Option<f64>,
+    input_latency: Option<f64>,
 }
 
 impl<T: FnMut(WindowEvent)> Display<T> {
@@ -102,47 +589,888 @@ impl<T: FnMut(WindowEvent)> Display<T> {
     ///
     /// # Panics
     ///
-    /// - On GLFW Errors.
+    /// - On GLFW Errors, unless the `log` feature is enabled, which logs them.
     /// - If cannot initialize GLFW.
     /// - If cannot get the primary monitor.
     /// - If cannot create the window.
-    /// - If cannot get the primary monitor's video mode.
+    /// - If cannot get the primary monitor's video mode in fullscreen mode.
     pub fn new(opt: Options, handler: T) -> Self {
-        let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS).expect("Could not initialize the GLFW!");
-        let (mut window, events) = glfw.with_primary_monitor(|glfw, monitor| {
+        Self::new_with_glfw(&mut init(), opt, handler)
+    }
+
+    /// Creates and sets up a new [glfw::Window] from the given GLFW, which
+    /// can be shared between many displays, like the one from [init] or the
+    /// [glfw](Self::glfw) of another display.
+    /// Needed for more than one window, since GLFW is initialized once.
+    /// The context of the new display becomes the current one.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot get the primary monitor.
+    /// - If cannot create the window.
+    /// - If cannot get the primary monitor's video mode in fullscreen mode.
+    pub fn new_with_glfw(glfw: &mut Glfw, opt: Options, handler: T) -> Self {
+        let (window, events) = glfw.with_primary_monitor(|glfw, monitor| {
             if let Some(monitor) = monitor {
                 opt.config(glfw);
-                opt.create(glfw, monitor)
+                opt.create(glfw, monitor, None)
+                    .expect("Could not create the window!")
             } else {
                 panic!("Could not get the primary monitor!");
             }
         });
-        window.set_all_polling(true);
-        window.make_current();
-        gl::load(|proc| glfw.get_proc_address_raw(proc));
-        opt.config_context(&mut glfw);
+        Self::from_window(glfw, opt, window, events, handler)
+    }
+
+    /// Creates and sets up a new [glfw::Window] whose context shares the
+    /// objects of the given window's context, on the primary monitor.
+    /// The context of the new display becomes the current one.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot get the primary monitor's video mode in fullscreen mode.
+    pub(crate) fn new_shared(
+        glfw: &mut Glfw,
+        opt: Options,
+        handler: T,
+        share: &Window,
+    ) -> Result<Self, DisplayError> {
+        let (window, events) = glfw
+            .with_primary_monitor(|glfw, monitor| {
+                monitor.map(|monitor| {
+                    opt.config(glfw);
+                    opt.create(glfw, monitor, Some(share))
+                })
+            })
+            .ok_or(DisplayError::NoMonitor)?
+            .ok_or(DisplayError::WindowCreation)?;
+        Ok(Self::from_window(glfw, opt, window, events, handler))
+    }
+
+    /// Sets up the display around the created window.
+    fn from_window(
+        glfw: &mut Glfw,
+        opt: Options,
+        mut window: Window,
+        events: Receiver<(f64, WindowEvent)>,
+        handler: T,
+    ) -> Self {
+        let unloaded_functions = opt.setup(glfw, &mut window);
+        let time_source = Box::new(GlfwTime(glfw.clone()));
+        let start = time_source.seconds();
+        let clock = Clock::new(start);
+        let cursor_inside = window.is_hovered();
+        let focused = window.is_focused();
+        let iconified = window.is_iconified();
         Self {
             window,
             handler,
             events,
+            text_input: String::new(),
+            frame_count: 0,
+            clock,
+            time_source,
+            unloaded_functions,
+            cursor_inside,
+            refresh: None,
+            focused,
+            background_fps: None,
+            last_swap: Instant::now(),
+            clicks: Clicks::new(),
+            held_scancodes: HashSet::new(),
+            gamma: None,
+            windowed_rect: None,
+            options: opt,
+            last_frame: start,
+            delta: 0.0,
+            max_delta: None,
+            capture: None,
+            adaptive_vsync: false,
+            frame_times: FrameTimes::new(60),
+            iconified,
+            skip_hidden: false,
+            recorder: None,
+            context_lost: Cell::new(false),
+            max_events: None,
+            frame_cap: None,
+            damage_swap: None,
+            monitor_changes: None,
+            ignore_key_repeat: false,
+            swap_group: 0,
+            focus_on_hover: false,
+            hover_focus_requested: false,
+            workers: Vec::new(),
+            render_scale: RenderScale::new(),
+            event_filter: None,
+            measure_latency: false,
+            pending_input: None,
+            input_latency: None,
+        }
+    }
+
+    /// Recreates the window and its context with the given options, while
+    /// keeping the handler and the rest of the display, like the clock and
+    /// the [refresh hook](Self::on_refresh).
+    /// Needed for applying the options that GLFW cannot change on an existing
+    /// context, like `msaa`, `debug_context` or `client_api`; thus, meant for
+    /// the "apply graphics settings" menus.
+    ///
+    /// The new context shares the objects of the old one, like the textures,
+    /// buffers and programs. **Container objects, like the vertex arrays and
+    /// the framebuffers, are not shared by OpenGL; they must be recreated.**
+    /// The new window is created on the monitor the display is on, and its
+    /// context becomes the current one; then, the old window is closed. Keeps the old window if the new one
+    /// cannot be created, and returns the error.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot get the monitor's video mode in fullscreen mode.
+    pub fn reconfigure(&mut self, opt: Options) -> Result<(), DisplayError> {
+        let mut glfw = self.glfw().clone();
+        opt.config(&mut glfw);
+        let old = &self.window;
+        let (mut window, events) = self
+            .with_monitor(|monitor| opt.create(&mut glfw, monitor, Some(old)))
+            .ok_or(DisplayError::NoMonitor)?
+            .ok_or(DisplayError::WindowCreation)?;
+        self.unloaded_functions = opt.setup(&mut glfw, &mut window);
+        gl::forget_blitter(self.window.window_ptr());
+        self.window = window;
+        self.events = events;
+        if self.refresh.is_some() {
+            self.install_refresh_callback();
+        }
+        self.cursor_inside = self.window.is_hovered();
+        self.focused = self.window.is_focused();
+        self.iconified = self.window.is_iconified();
+        self.context_lost.set(false);
+        self.damage_swap = None;
+        self.windowed_rect = None;
+        self.swap_group = 0;
+        self.hover_focus_requested = false;
+        if self.adaptive_vsync {
+            let interval = SwapInterval::Sync(self.focused as u32);
+            self.glfw_mut().set_swap_interval(interval);
         }
+        self.held_scancodes.clear();
+        self.capture = None;
+        self.options = opt;
+        if self.render_scale.is_active() {
+            // The old window was destroyed with its context when replaced, and
+            // the new context only shares the attachments of the internal
+            // framebuffer, whose name is unused in it; thus, dropping it is
+            // safe.
+            self.render_scale.drop_target();
+            self.bind_render_target();
+        }
+        Ok(())
     }
 
     /// Renders the drawn contents and clears the color buffer for next frame.
     /// Will wait for a monitor refresh with VSync enabled.
+    /// Same as a [swap](Self::swap) followed by a [clear](Self::clear), after
+    /// the [commands of the workers](Self::run_worker_commands).
     pub fn render(&mut self) {
-        self.window.swap_buffers();
+        self.run_worker_commands();
+        self.swap();
+        self.clear();
+    }
+
+    /// Shows the drawn contents by swapping the front and back buffers.
+    /// Will wait for a monitor refresh with VSync enabled.
+    /// Does not clear anything; thus, the contents of the new back buffer are
+    /// whatever the platform leaves after the swap, which is usually the frame
+    /// before the last one.
+    /// Waits before swapping while the window is not focused if there is a
+    /// [background frame rate](Self::set_background_fps), and always if there
+    /// is a cap from the [frame pacing](Self::set_frame_pacing).
+    /// Does not swap while the window is iconified if
+    /// [skipping](Self::set_skip_render_when_hidden).
+    /// Upscales the frame to the window first with a
+    /// [render scale](Self::set_render_scale).
+    pub fn swap(&mut self) {
+        self.present(None);
+    }
+
+    /// Shows the drawn contents like a [swap](Self::swap), but tells the
+    /// platform that only the given rectangles changed since the last frame,
+    /// as `(x, y, width, height)` in framebuffer pixels from the bottom left
+    /// corner like the viewport. Then the compositor can update only them,
+    /// which saves power for the mostly static editors and tools.
+    /// An empty slice means the whole window.
+    ///
+    /// Uses `EGL_KHR_swap_buffers_with_damage` or its `EXT` version, which is
+    /// only available for the contexts that are created by EGL, like on
+    /// Wayland or with `WindowHint::ContextCreationApi` in the
+    /// [extra hints](Options::extra_hints). Falls back to a full swap
+    /// otherwise. The contents outside the rectangles must still be drawn
+    /// unless the buffers are preserved.
+    pub fn swap_with_damage(&mut self, rects: &[(i32, i32, i32, i32)]) {
+        self.present(Some(rects));
+    }
+
+    /// Does the swap with the damage if any.
+    fn present(&mut self, damage: Option<&[(i32, i32, i32, i32)]>) {
+        let background_fps = self.background_fps.filter(|_| !self.focused);
+        let fps = match (background_fps, self.frame_cap) {
+            (Some(background_fps), Some(frame_cap)) => Some(background_fps.min(frame_cap)),
+            (background_fps, frame_cap) => background_fps.or(frame_cap),
+        };
+        if let Some(fps) = fps {
+            let frame = Duration::from_secs_f64(1.0 / fps);
+            let elapsed = self.last_swap.elapsed();
+            if elapsed < frame {
+                thread::sleep(frame - elapsed);
+            }
+        }
+        if !(self.skip_hidden && self.iconified) {
+            self.upscale();
+            let swapped = damage.is_some_and(|rects| self.swap_damaged(rects));
+            if !swapped {
+                self.window.swap_buffers();
+            }
+        }
+        if self.render_scale.is_active() {
+            self.bind_render_target();
+        }
+        if self.measure_latency {
+            if let Some(input) = self.pending_input.take() {
+                gl::Finish();
+                self.input_latency = Some(self.glfw().get_time() - input);
+            }
+        }
+        self.last_swap = Instant::now();
+        self.frame_count = self.frame_count.wrapping_add(1);
+        let time = self.time();
+        self.delta = time - self.last_frame;
+        self.last_frame = time;
+        self.frame_times.push(self.delta);
+    }
+
+    /// Clears the color buffer.
+    /// Calling this after a [swap](Self::swap) clears the new back buffer, which
+    /// is the one the next frame is drawn to. Calling it before the swap clears
+    /// the contents that were about to be shown.
+    pub fn clear(&mut self) {
         gl::Clear(gl::COLOR_BUFFER_BIT);
     }
 
+    /// Runs the given [compute](gl::shader::compute) program with the given
+    /// amount of work groups in each dimension, by using it and calling
+    /// `glDispatchCompute`. The program stays in use afterwards.
+    /// The writes of the shader are not visible to the following commands
+    /// until a [memory barrier](Self::memory_barrier) for them.
+    pub fn dispatch_compute(&mut self, program: u32, x: u32, y: u32, z: u32) {
+        gl::UseProgram(program);
+        gl::DispatchCompute(x, y, z);
+    }
+
+    /// Makes the preceding shader writes visible to the kinds of the following
+    /// commands in the given bits, like `gl::SHADER_STORAGE_BARRIER_BIT` for
+    /// reading them in the shaders, or `gl::BUFFER_UPDATE_BARRIER_BIT` for
+    /// reading them back from the buffers.
+    pub fn memory_barrier(&mut self, bits: u32) {
+        gl::MemoryBarrier(bits);
+    }
+
     /// Polls the [window events](glfw::WindowEvent) and calls the handler.
+    /// Events are passed to the [recorder](Self::record_events) first if any,
+    /// then through the [filter](Self::set_event_filter) if any.
+    /// Handles at most the [maximum](Self::set_max_events_per_update) amount
+    /// of events if set.
+    /// [Repositions](Self::set_auto_reposition_on_monitor_change) the window
+    /// before them if enabled.
     pub fn update(&mut self) {
-        self.glfw_mut().poll_events();
-        for (_, event) in glfw::flush_messages(&self.events) {
-            (self.handler)(event);
+        self.pump();
+        self.handle_events();
+    }
+
+    /// Handles the polled events like [update](Self::update) does after the
+    /// pump.
+    pub(crate) fn handle_events(&mut self) {
+        self.reposition_on_monitor_change();
+        let max = self.max_events.unwrap_or(usize::MAX);
+        for _ in 0..max {
+            let Ok((time, event)) = self.events.try_recv() else {
+                break;
+            };
+            if let Some(recorder) = &mut self.recorder {
+                recorder(time, event.clone());
+            }
+            self.handle(time, event);
         }
     }
 
+    /// Keeps the repeated [key events](glfw::WindowEvent::Key) that are sent
+    /// while a key is held from the handler if enabled; thus, the handler
+    /// gets a single press per press, like for moving once in a menu.
+    /// The [text input](glfw::WindowEvent::Char) still repeats.
+    /// Disabled by default.
+    pub fn set_ignore_key_repeat(&mut self, on: bool) {
+        self.ignore_key_repeat = on;
+    }
+
+    /// Measures the [input latency](Self::last_input_latency) if enabled.
+    /// Calls `glFinish` after the [swaps](Self::swap) that follow an input,
+    /// which stalls the CPU until the GPU is done; thus, lowers the frame
+    /// rate of the heavy scenes, and is meant for tuning the
+    /// [pacing](Self::set_frame_pacing) rather than for the final builds.
+    /// Disabled by default.
+    pub fn set_measure_input_latency(&mut self, on: bool) {
+        self.measure_latency = on;
+        self.pending_input = None;
+        self.input_latency = None;
+    }
+
+    /// Returns the seconds between the latest input event, like a key press
+    /// or a cursor movement, and the end of the first
+    /// [swap](Self::swap) after it, which is measured while
+    /// [enabled](Self::set_measure_input_latency). `None` before the first
+    /// measurement.
+    /// Uses the timestamps of the events on the GLFW timer, rather than the
+    /// [display's clock](Self::time). The swap ends when the GPU finished
+    /// the frame and the platform took it; the compositor and the monitor add
+    /// their own latency after that, which cannot be measured here.
+    pub fn last_input_latency(&self) -> Option<f64> {
+        self.input_latency
+    }
+
+    /// Limits the amount of events that an [update](Self::update) handles,
+    /// leaving the rest in the [receiver](Self::events) for the next ones.
+    /// Prevents the spiral where the thousands of events that are queued
+    /// during a stall make the next frame long as well, which queues even
+    /// more events. In exchange, the events are handled later during the
+    /// floods, which adds latency to the input.
+    /// Consider `Some(256)`, which is far more than a frame gets normally.
+    /// Not limited if `None`, which is the default.
+    pub fn set_max_events_per_update(&mut self, max: Option<usize>) {
+        self.max_events = max;
+    }
+
+    /// Passes the given events with their timestamps through the display as
+    /// if they were polled by [update](Self::update): the tracked state, like
+    /// the [held keys](Self::is_scancode_down), is updated and the handler
+    /// gets them. They are not [recorded](Self::record_events).
+    /// Meant for the deterministic replays of recorded sessions in tests and
+    /// bug reproductions, together with a [FakeTime](time::FakeTime) that is
+    /// set to the timestamps. Does not poll or wait; thus, the events should
+    /// be replayed frame by frame.
+    pub fn replay_events(&mut self, events: &[(f64, WindowEvent)]) {
+        for (time, event) in events {
+            self.handle(*time, event.clone());
+        }
+    }
+
+    /// Passes all the polled events with their timestamps to the given sink
+    /// in [update](Self::update), before they are handled.
+    /// The sink replaces the previous one if any. Consider collecting them
+    /// for [replaying](Self::replay_events) them later.
+    pub fn record_events(&mut self, sink: impl FnMut(f64, WindowEvent) + 'static) {
+        self.recorder = Some(Box::new(sink));
+    }
+
+    /// Stops passing the events to the [recorder](Self::record_events), which
+    /// is dropped.
+    pub fn stop_recording(&mut self) {
+        self.recorder = None;
+    }
+
+    /// Passes the events through the given filter before they are handled,
+    /// which returns the event to handle, possibly modified, or `None` for
+    /// dropping it. Meant for remapping the keys for accessibility, logging,
+    /// or swapping the events in tests.
+    /// Applies to the events of [update](Self::update) after they are
+    /// [recorded](Self::record_events), and to the
+    /// [replayed](Self::replay_events) ones; the tracked state, like the
+    /// [held keys](Self::is_scancode_down), and the handler only see the
+    /// filtered events. The filter replaces the previous one if any.
+    pub fn set_event_filter(
+        &mut self,
+        filter: impl FnMut(WindowEvent) -> Option<WindowEvent> + 'static,
+    ) {
+        self.event_filter = Some(Box::new(filter));
+    }
+
+    /// Stops passing the events through the
+    /// [filter](Self::set_event_filter), which is dropped.
+    pub fn remove_event_filter(&mut self) {
+        self.event_filter = None;
+    }
+
+    /// Updates the tracked state with the event and calls the handler.
+    fn handle(&mut self, time: f64, event: WindowEvent) {
+        let event = match &mut self.event_filter {
+            Some(filter) => match filter(event) {
+                Some(event) => event,
+                None => return,
+            },
+            None => event,
+        };
+        self.clicks.record(time, &event);
+        if self.measure_latency && is_input(&event) {
+            self.pending_input = Some(time);
+        }
+        if let (WindowEvent::CursorPos(x, y), Some(capture)) = (&event, &mut self.capture) {
+            capture.moved(*x, *y, self.window.get_size());
+        }
+        match event {
+            WindowEvent::Char(c) => self.text_input.push(c),
+            WindowEvent::CursorEnter(entered) => {
+                if entered && !self.cursor_inside {
+                    self.focus_on_enter();
+                } else if !entered {
+                    self.hover_focus_requested = false;
+                }
+                self.cursor_inside = entered;
+            }
+            WindowEvent::Iconify(iconified) => self.iconified = iconified,
+            WindowEvent::Focus(focused) => {
+                self.focused = focused;
+                self.hover_focus_requested = false;
+                if self.adaptive_vsync {
+                    let interval = SwapInterval::Sync(focused as u32);
+                    self.window.glfw.set_swap_interval(interval);
+                }
+            }
+            WindowEvent::Key(_, scancode, Action::Release, _) => {
+                self.held_scancodes.remove(&scancode);
+            }
+            WindowEvent::Key(_, scancode, _, _) => {
+                self.held_scancodes.insert(scancode);
+            }
+            WindowEvent::FramebufferSize(..) if self.render_scale.is_active() => {
+                self.bind_render_target();
+            }
+            WindowEvent::FramebufferSize(width, height) if self.window.is_resizable() => {
+                gl::Viewport(0, 0, width, height);
+            }
+            _ => {}
+        }
+        if self.ignore_key_repeat && matches!(event, WindowEvent::Key(_, _, Action::Repeat, _)) {
+            return;
+        }
+        (self.handler)(event);
+    }
+
+    /// Polls the [window events](glfw::WindowEvent) without calling the handler.
+    /// The events stay in the [receiver](Self::events) until they are flushed
+    /// by the user or the next [update](Self::update).
+    /// Refresh events are the exception when there is a [refresh
+    /// hook](Self::on_refresh), which handles them while polling.
+    pub fn pump(&mut self) {
+        self.poll_events();
+    }
+
+    /// Returns the receiver of the polled [window events](glfw::WindowEvent)
+    /// with their times, which can be drained by [glfw::flush_messages].
+    /// Events that are drained here do not reach the handler, and are not
+    /// recorded by the display.
+    pub fn events(&self) -> &Receiver<(f64, WindowEvent)> {
+        &self.events
+    }
+
+    /// Returns the amount of frames that were shown, which is incremented by
+    /// every [swap](Self::swap); thus, by every [render](Self::render).
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Returns the time of the display's clock in seconds, which is the time
+    /// of the [source](Self::set_time_source), the GLFW timer by default,
+    /// without the durations the clock was [paused](Self::pause_clock).
+    pub fn time(&self) -> f64 {
+        self.clock.time(self.time_source.seconds())
+    }
+
+    /// Returns the seconds passed on the display's clock since the display was
+    /// created, which does not count the paused durations.
+    pub fn elapsed(&self) -> f64 {
+        self.clock.elapsed(self.time_source.seconds())
+    }
+
+    /// Freezes the display's clock, which is used for the [time](Self::time),
+    /// the [elapsed](Self::elapsed) time and the [min_timer::Now] implementation.
+    /// The time source keeps running. Consider pausing while a pause menu is open,
+    /// so there is no giant frame after resuming.
+    pub fn pause_clock(&mut self) {
+        let raw = self.time_source.seconds();
+        self.clock.pause(raw);
+    }
+
+    /// Continues the display's clock from where it was
+    /// [paused](Self::pause_clock).
+    pub fn resume_clock(&mut self) {
+        let raw = self.time_source.seconds();
+        self.clock.resume(raw);
+    }
+
+    /// Returns whether the display's clock is [paused](Self::pause_clock).
+    pub fn is_clock_paused(&self) -> bool {
+        self.clock.is_paused()
+    }
+
+    /// Replaces the [TimeSource] of the display's clock, like with a
+    /// [FakeTime](time::FakeTime) for deterministic tests.
+    /// Restarts the clock, which is not paused afterwards; thus, the
+    /// [elapsed](Self::elapsed) time starts from zero.
+    pub fn set_time_source(&mut self, source: impl TimeSource + 'static) {
+        self.clock = Clock::new(source.seconds());
+        self.time_source = Box::new(source);
+        self.last_frame = self.time();
+        self.delta = 0.0;
+    }
+
+    /// Returns the seconds passed on the display's clock between the last two
+    /// [swaps](Self::swap), which is the duration of the last frame, at most
+    /// the [maximum](Self::set_max_delta). Zero before the first swap.
+    /// Does not count the durations the clock was [paused](Self::pause_clock).
+    pub fn delta(&self) -> f64 {
+        match self.max_delta {
+            Some(max) => self.delta.min(max),
+            None => self.delta,
+        }
+    }
+
+    /// Returns the frame rate averaged over the last
+    /// [frames](Self::set_fps_window), which is what is shown in the title
+    /// bars and the overlays, since the frame rate of a single frame jitters.
+    /// Tracked from the [deltas](Self::delta) without the maximum in the
+    /// [swaps](Self::swap). Zero before the first swap.
+    pub fn avg_fps(&self) -> f64 {
+        self.frame_times.average_fps()
+    }
+
+    /// Sets the amount of frames the [average frame rate](Self::avg_fps) is
+    /// over, which is 60 by default. Forgets the recorded frames.
+    ///
+    /// # Panics
+    ///
+    /// - If the amount is zero.
+    pub fn set_fps_window(&mut self, frames: usize) {
+        assert!(frames > 0, "Frame rate window must not be empty!");
+        self.frame_times = FrameTimes::new(frames);
+    }
+
+    /// Clamps the [delta](Self::delta) to the given seconds, so a hitch like
+    /// stopping at a breakpoint or dragging the window does not make the
+    /// simulations take a giant step.
+    /// Time appears to slow down during the hitches instead, which is what
+    /// the simulations want.
+    /// Consider `Some(0.1)` for physics.
+    /// Not clamped if `None`, which is the default.
+    pub fn set_max_delta(&mut self, max: Option<f64>) {
+        self.max_delta = max;
+    }
+
+    /// Returns the characters typed since the last call.
+    /// Collected from the [character events](glfw::WindowEvent::Char) in
+    /// [update](Self::update), which are still passed to the handler.
+    pub fn take_text_input(&mut self) -> String {
+        std::mem::take(&mut self.text_input)
+    }
+
+    /// Returns whether the cursor is inside the window's content area.
+    /// Tracked from the [cursor enter events](glfw::WindowEvent::CursorEnter)
+    /// in [update](Self::update), which are still passed to the handler.
+    pub fn is_cursor_inside(&self) -> bool {
+        self.cursor_inside
+    }
+
+    /// Returns whether the window has the input focus.
+    /// Tracked from the [focus events](glfw::WindowEvent::Focus) in
+    /// [update](Self::update), which are still passed to the handler.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Focuses the window when the cursor enters it while it is not
+    /// [focused](Self::is_focused) if enabled, which is the focus follows the
+    /// mouse behavior of the tools with many windows.
+    /// Detected from the [cursor enter events](glfw::WindowEvent::CursorEnter)
+    /// in [update](Self::update). The focus is requested once per enter until
+    /// a focus event or a leave, so the spurious enters that some window
+    /// managers send while raising the window cannot make the windows steal
+    /// the focus from each other in a loop. Not requested while iconified.
+    /// The window managers with focus stealing prevention can refuse it.
+    /// Disabled by default.
+    pub fn set_focus_on_hover(&mut self, on: bool) {
+        self.focus_on_hover = on;
+        self.hover_focus_requested = false;
+    }
+
+    /// Requests the focus for the entered cursor if focusing on hover.
+    fn focus_on_enter(&mut self) {
+        if self.focus_on_hover && !self.focused && !self.iconified && !self.hover_focus_requested {
+            self.hover_focus_requested = true;
+            self.window.focus();
+        }
+    }
+
+    /// Limits the frame rate to the given one while the window is not
+    /// [focused](Self::is_focused), by sleeping in the [swap](Self::swap),
+    /// which saves power when the application is in the background.
+    /// The full frame rate is back as soon as the window is focused.
+    /// Consider `Some(10.0)` for a window that still shows something alive.
+    /// Not limited if `None`, which is the default.
+    ///
+    /// # Panics
+    ///
+    /// - If the frame rate is not positive.
+    pub fn set_background_fps(&mut self, fps: Option<f64>) {
+        assert!(
+            fps.is_none_or(|fps| fps > 0.0),
+            "Background frame rate must be positive!"
+        );
+        self.background_fps = fps;
+    }
+
+    /// Switches the swap interval with the focus if enabled: waits for a
+    /// monitor refresh between frames while the window is
+    /// [focused](Self::is_focused), and renders unbounded while it is not.
+    /// Switched on the [focus events](glfw::WindowEvent::Focus) in
+    /// [update](Self::update); thus, the handler can set another interval
+    /// after them, like the opposite for benchmarking in the background.
+    /// The swap interval of the [options](Options::swap_interval) is back
+    /// when disabled, which is the default.
+    /// Sets the interval of the current context, which must be the display's.
+    pub fn set_adaptive_vsync_on_focus(&mut self, on: bool) {
+        self.adaptive_vsync = on;
+        let interval = if on {
+            SwapInterval::Sync(self.focused as u32)
+        } else {
+            self.options.swap_interval()
+        };
+        self.glfw_mut().set_swap_interval(interval);
+    }
+
+    /// Sets the swap interval from the given mode, which overrides the one of
+    /// the [options](Options::swap_interval) and the
+    /// [adaptive vsync](Self::set_adaptive_vsync_on_focus).
+    /// [Adaptive](VsyncMode::Adaptive) falls back to [On](VsyncMode::On) with
+    /// a warning, printed or logged with the `log` feature, if
+    /// `WGL_EXT_swap_control_tear` or `GLX_EXT_swap_control_tear` is not
+    /// supported. Returns the mode that is set.
+    /// Does not change the cap of the [frame pacing](Self::set_frame_pacing).
+    /// Sets the interval of the current context, which must be the display's.
+    pub fn set_vsync_mode(&mut self, mode: VsyncMode) -> VsyncMode {
+        let tear = ["WGL_EXT_swap_control_tear", "GLX_EXT_swap_control_tear"];
+        let mode = match mode {
+            VsyncMode::Adaptive if !tear.iter().any(|e| self.glfw().extension_supported(e)) => {
+                warn("Adaptive vsync is not supported, falling back to vsync!");
+                VsyncMode::On
+            }
+            mode => mode,
+        };
+        self.adaptive_vsync = false;
+        self.glfw_mut().set_swap_interval(match mode {
+            VsyncMode::Off => SwapInterval::None,
+            VsyncMode::On => SwapInterval::Sync(1),
+            VsyncMode::Adaptive => SwapInterval::Adaptive,
+        });
+        mode
+    }
+
+    /// Sets the swap interval and the frame rate cap together from the given
+    /// way of pacing, which overrides the swap interval of the
+    /// [options](Options::swap_interval) and the
+    /// [adaptive vsync](Self::set_adaptive_vsync_on_focus).
+    /// The cap sleeps in the [swap](Self::swap), which is as precise as the
+    /// sleeps of the platform, usually around a millisecond.
+    /// [MatchRefresh](FramePacing::MatchRefresh) reads the refresh rate once,
+    /// and falls back to [Vsync](FramePacing::Vsync) if it is unknown.
+    /// Sets the interval of the current context, which must be the display's.
+    ///
+    /// # Panics
+    ///
+    /// - If the cap is not positive.
+    pub fn set_frame_pacing(&mut self, pacing: FramePacing) {
+        let (vsync, cap) = match pacing {
+            FramePacing::Vsync => (true, None),
+            FramePacing::Uncapped => (false, None),
+            FramePacing::CapTo(fps) => {
+                assert!(fps > 0.0, "Frame rate cap must be positive!");
+                (false, Some(fps))
+            }
+            FramePacing::MatchRefresh => match self.refresh_rate() {
+                Some(rate) if rate > 0 => (false, Some(rate as f64)),
+                _ => (true, None),
+            },
+        };
+        self.adaptive_vsync = false;
+        self.frame_cap = cap;
+        self.glfw_mut()
+            .set_swap_interval(SwapInterval::Sync(vsync as u32));
+    }
+
+    /// Resizes the window to the given size in screen coordinates, and fits
+    /// the viewport to its framebuffer, which is larger on the HiDPI screens.
+    /// Works even if the window is not [resizable](Options::resizable) by the
+    /// user. Stores the size with the other options of the display.
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.window.set_size(width as i32, height as i32);
+        self.options.width = width;
+        self.options.height = height;
+        self.fit_viewport();
+    }
+
+    /// Places the window at the given position with the given size in screen
+    /// coordinates, which leaves [fullscreen](Self::set_fullscreen) mode if it
+    /// is in it. Keeps the OpenGL context, and fits the viewport to the new
+    /// size.
+    /// Meant for restoring a saved window layout.
+    pub fn set_windowed(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        self.window
+            .set_monitor(glfw::WindowMode::Windowed, x, y, width, height, None);
+        self.windowed_rect = None;
+        self.fit_viewport();
+    }
+
+    /// Sets the viewport to the whole framebuffer with its current size.
+    /// Done automatically in [update](Self::update) on the
+    /// [framebuffer size events](glfw::WindowEvent::FramebufferSize) of a
+    /// [resizable](Options::resizable) window; otherwise, call this after
+    /// changing the size.
+    pub fn fit_viewport(&mut self) {
+        let (width, height) = self.window.get_framebuffer_size();
+        gl::Viewport(0, 0, width, height);
+    }
+
+    /// Minimizes the window to the taskbar or the dock.
+    /// In fullscreen mode, the original video mode of the monitor is restored
+    /// until the window is [restored](Self::restore).
+    pub fn iconify(&mut self) {
+        self.window.iconify();
+    }
+
+    /// Maximizes the window to the work area of its monitor.
+    /// Does nothing in fullscreen mode.
+    pub fn maximize(&mut self) {
+        self.window.maximize();
+    }
+
+    /// Restores the window from being [iconified](Self::iconify) or
+    /// [maximized](Self::maximize).
+    pub fn restore(&mut self) {
+        self.window.restore();
+    }
+
+    /// Returns whether the window is [iconified](Self::iconify).
+    pub fn is_iconified(&self) -> bool {
+        self.window.is_iconified()
+    }
+
+    /// Skips the buffer swaps in [swap](Self::swap) and
+    /// [render](Self::render) while the window is iconified if enabled, which
+    /// saves the GPU work of the frames that nobody sees, and avoids the
+    /// driver stalls of swapping a hidden window on some platforms.
+    /// The frames are still counted and timed. Since the swaps do not wait for
+    /// the monitor refreshes then, consider a
+    /// [background frame rate](Self::set_background_fps) as well.
+    /// Iconification is tracked from the
+    /// [iconify events](glfw::WindowEvent::Iconify) in
+    /// [update](Self::update). Disabled by default.
+    pub fn set_skip_render_when_hidden(&mut self, on: bool) {
+        self.skip_hidden = on;
+    }
+
+    /// Returns whether the window is [maximized](Self::maximize).
+    pub fn is_maximized(&self) -> bool {
+        self.window.is_maximized()
+    }
+
+    /// Converts a position in window coordinates, like the ones in the
+    /// [cursor events](glfw::WindowEvent::CursorPos), to framebuffer pixels.
+    /// These differ on HiDPI monitors, for example by a factor of 2 on Retina
+    /// Macs; thus, use this to find the pixel under the cursor for picking.
+    /// Uses the current ratio of the framebuffer size to the window size,
+    /// rather than the monitor's content scale, since some platforms already
+    /// report window coordinates in pixels.
+    pub fn logical_to_physical(&self, x: f64, y: f64) -> (f64, f64) {
+        let (sx, sy) = self.pixel_ratio();
+        (x * sx, y * sy)
+    }
+
+    /// Converts a position in framebuffer pixels to window coordinates.
+    /// Inverse of [logical_to_physical](Self::logical_to_physical).
+    pub fn physical_to_logical(&self, x: f64, y: f64) -> (f64, f64) {
+        let (sx, sy) = self.pixel_ratio();
+        (x / sx, y / sy)
+    }
+
+    /// Returns an orthographic projection for 2D drawing in framebuffer pixels,
+    /// which maps `(0, 0)` to the top-left corner and the framebuffer size to
+    /// the bottom-right corner. Computed from the current framebuffer size;
+    /// thus, call it again after the framebuffer is resized.
+    #[cfg(feature = "math")]
+    pub fn pixel_perfect_ortho(&self) -> gl::math::Mat4 {
+        let (width, height) = self.window.get_framebuffer_size();
+        gl::math::ortho(0.0, width as f32, height as f32, 0.0, -1.0, 1.0)
+    }
+
+    /// Returns the point of the world under the given position in window
+    /// coordinates, like the ones in the
+    /// [cursor events](glfw::WindowEvent::CursorPos), for the given camera
+    /// that covers the whole framebuffer. Converted to framebuffer pixels
+    /// first, with the current framebuffer size.
+    #[cfg(feature = "math")]
+    pub fn camera2d_screen_to_world(
+        &self,
+        camera: &gl::math::Camera2D,
+        x: f64,
+        y: f64,
+    ) -> gl::math::Vec2 {
+        let (x, y) = self.logical_to_physical(x, y);
+        let (width, height) = self.window.get_framebuffer_size();
+        camera.screen_to_world(x as f32, y as f32, width as f32, height as f32)
+    }
+
+    /// Returns the framebuffer pixels per window coordinate on both axes,
+    /// which are 1 while the window is minimized.
+    fn pixel_ratio(&self) -> (f64, f64) {
+        let (fb_width, fb_height) = self.window.get_framebuffer_size();
+        let (width, height) = self.window.get_size();
+        if fb_width <= 0 || fb_height <= 0 || width <= 0 || height <= 0 {
+            return (1.0, 1.0);
+        }
+        (
+            fb_width as f64 / width as f64,
+            fb_height as f64 / height as f64,
+        )
+    }
+
+    /// Returns the position and the size of the primary monitor's work area,
+    /// which is the part that is not occupied by the taskbars, docks or menu
+    /// bars, as `(x, y, width, height)` in screen coordinates.
+    /// Windowed displays are centered in this area.
+    ///
+    /// # Panics
+    ///
+    /// - If cannot get the primary monitor.
+    pub fn work_area(&self) -> (i32, i32, i32, i32) {
+        self.glfw()
+            .clone()
+            .with_primary_monitor(|_, monitor| monitor.map(|monitor| monitor.get_workarea()))
+            .expect("Could not get the primary monitor!")
+    }
+
+    /// Returns the refresh rate of the monitor the display is on in hertz.
+    /// In fullscreen mode, this is the rate of the chosen video mode.
+    pub fn refresh_rate(&self) -> Option<u32> {
+        self.with_monitor(|monitor| monitor.get_video_mode())
+            .flatten()
+            .map(|vidmode| vidmode.refresh_rate)
+    }
+
+    /// Returns the names of the core OpenGL functions that could not be loaded,
+    /// which crash if called. Should be empty on a driver that really supports
+    /// OpenGL 4.6; otherwise, a warning is printed, or logged with the `log`
+    /// feature, when the display is created. There is no warning with
+    /// [ClientApi::OpenGlEs], which misses many desktop functions anyway.
+    /// Missing extension functions are not included.
+    pub fn unloaded_functions(&self) -> &[&'static str] {
+        &self.unloaded_functions
+    }
+
     /// Returns the [glfw::Window].
     pub fn window(&self) -> &Window {
         &self.window
@@ -164,11 +1492,33 @@ impl<T: FnMut(WindowEvent)> Display<T> {
     }
 }
 
+impl<T: FnMut(WindowEvent)> Drop for Display<T> {
+    fn drop(&mut self) {
+        // Restores the gamma before the fields holding the GLFW are dropped,
+        // which can terminate it.
+        self.gamma = None;
+        gl::forget_blitter(self.window.window_ptr());
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use glfw::WindowEvent;
+    use glfw::{CursorMode, WindowEvent};
+
+    use crate::{gl, ClientApi, Display, Options, QualityPreset};
 
-    use crate::{gl, Display, Options};
+    #[test]
+    fn presets_trade_quality() {
+        let low = Options::preset(QualityPreset::Low);
+        let high = Options::preset(QualityPreset::High);
+        assert_eq!((low.msaa, low.vsync), (None, false));
+        assert_eq!((high.msaa, high.vsync), (Some(8), true));
+        let titled = Options {
+            title: "Game".into(),
+            ..Options::preset(QualityPreset::Medium)
+        };
+        assert_eq!(titled.msaa, Some(4));
+    }
 
     #[test]
     #[ignore]
@@ -187,8 +1537,21 @@ mod tests {
                 title: "Display Test".into(),
                 fullscreen: false,
                 decorated: true,
+                resizable: false,
+                lock_aspect: None,
                 msaa: Some(16),
                 vsync: true,
+                swap_interval: None,
+                debug_context: cfg!(debug_assertions),
+                no_error_context: false,
+                center_cursor: true,
+                start_cursor_mode: CursorMode::Normal,
+                refresh_rate: None,
+                client_api: ClientApi::OpenGl,
+                extra_hints: Vec::new(),
+                set_initial_viewport: true,
+                retina_framebuffer: true,
+                graphics_switching: true,
             },
             // WindowEvent handling...
             |event| {
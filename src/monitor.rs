@@ -0,0 +1,560 @@
+use std::{
+    cmp::Reverse,
+    os::raw::c_int,
+    ptr, slice,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use glfw::{ffi, Context, GammaRamp, Monitor, VidMode, WindowEvent};
+
+use crate::{center, platform, Display};
+
+/// Assumed DPI of the monitors that do not report their physical size, which
+/// is the traditional DPI of Windows.
+pub const DEFAULT_DPI: f64 = 96.0;
+
+/// Rotation of a monitor from its natural orientation, in the direction that
+/// the platform reports it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Rotation {
+    /// Not rotated, which is also reported when the rotation is unknown.
+    #[default]
+    Normal,
+    /// Rotated by 90 degrees, which makes a landscape monitor portrait.
+    Rot90,
+    /// Upside down.
+    Rot180,
+    /// Rotated by 270 degrees, which makes a landscape monitor portrait.
+    Rot270,
+}
+
+/// Snapshot of the properties of a monitor, which do not change when the
+/// monitor does.
+#[derive(Clone, Debug)]
+pub struct MonitorInfo {
+    /// Human readable name of the monitor, which is not unique.
+    pub name: Option<String>,
+    /// Position of the top-left corner of the monitor in screen coordinates.
+    pub position: (i32, i32),
+    /// Position and size of the area that is not occupied by the taskbars,
+    /// docks or menu bars, as `(x, y, width, height)` in screen coordinates.
+    pub work_area: (i32, i32, i32, i32),
+    /// Physical size of the monitor in millimeters, which is `(0, 0)` if the
+    /// driver does not report it.
+    pub physical_size: (i32, i32),
+    /// Current video mode of the monitor.
+    pub video_mode: Option<VidMode>,
+    /// Ratio of the current DPI to the platform's default DPI on both axes.
+    pub content_scale: (f32, f32),
+}
+
+impl MonitorInfo {
+    /// Takes a snapshot of the given monitor.
+    pub fn new(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.get_name(),
+            position: monitor.get_pos(),
+            work_area: monitor.get_workarea(),
+            physical_size: monitor.get_physical_size(),
+            video_mode: monitor.get_video_mode(),
+            content_scale: monitor.get_content_scale(),
+        }
+    }
+
+    /// Returns the current rotation of the monitor, which is looked up again
+    /// by the position of the snapshot among the connected monitors.
+    /// Queried natively through RandR on X11 and the display settings on
+    /// Windows, since GLFW does not report it; always
+    /// [Normal](Rotation::Normal) on the other platforms, or if the monitor is
+    /// not connected anymore.
+    /// Meant for the kiosks with rotated displays.
+    pub fn rotation(&self) -> Rotation {
+        self.find()
+            .map_or(Rotation::Normal, platform::monitor_rotation)
+    }
+
+    /// Returns the video modes that the monitor supports, one per resolution
+    /// with its highest refresh rate and color depth, sorted by the width and
+    /// then the height. The monitor is looked up again like in
+    /// [rotation](Self::rotation); thus, the modes are empty if it is not
+    /// connected anymore.
+    /// Meant for listing the resolutions in the settings menus, since
+    /// requesting an unsupported one can fail the fullscreen window creation.
+    pub fn video_modes(&self) -> Vec<VidMode> {
+        let Some(monitor) = self.find() else {
+            return Vec::new();
+        };
+        let modes = unsafe {
+            let mut count = 0;
+            let modes = ffi::glfwGetVideoModes(monitor, &mut count);
+            if modes.is_null() {
+                return Vec::new();
+            }
+            slice::from_raw_parts(modes, count as usize)
+        };
+        distinct_resolutions(
+            modes
+                .iter()
+                .map(|mode| VidMode {
+                    width: mode.width as u32,
+                    height: mode.height as u32,
+                    red_bits: mode.redBits as u32,
+                    green_bits: mode.greenBits as u32,
+                    blue_bits: mode.blueBits as u32,
+                    refresh_rate: mode.refreshRate as u32,
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the [video mode](Self::video_modes) whose resolution is the
+    /// nearest to the given one, which is the given one itself if supported.
+    /// From the equally near ones, the smaller resolution is picked.
+    /// `None` if there are no modes.
+    pub fn closest_mode(&self, width: u32, height: u32) -> Option<VidMode> {
+        closest(&self.video_modes(), width, height)
+    }
+
+    /// Returns the connected monitor at the position of the snapshot.
+    fn find(&self) -> Option<*mut ffi::GLFWmonitor> {
+        connected().into_iter().find(|&monitor| {
+            let (mut x, mut y) = (0, 0);
+            unsafe { ffi::glfwGetMonitorPos(monitor, &mut x, &mut y) };
+            (x, y) == self.position
+        })
+    }
+
+    /// Returns the horizontal dots per inch, computed from the physical width
+    /// and the width of the current video mode.
+    /// Returns [DEFAULT_DPI] if either of them is unknown, since some drivers
+    /// report zero physical size.
+    pub fn dpi(&self) -> f64 {
+        let millimeters = self.physical_size.0;
+        match &self.video_mode {
+            Some(mode) if millimeters > 0 && mode.width > 0 => {
+                mode.width as f64 / (millimeters as f64 / 25.4)
+            }
+            _ => DEFAULT_DPI,
+        }
+    }
+}
+
+/// Sorts the modes by the resolution, and keeps the one with the highest
+/// refresh rate, then the highest color depth, for each resolution.
+fn distinct_resolutions(mut modes: Vec<VidMode>) -> Vec<VidMode> {
+    modes.sort_by_key(|mode| {
+        let depth = mode.red_bits + mode.green_bits + mode.blue_bits;
+        (
+            mode.width,
+            mode.height,
+            Reverse(mode.refresh_rate),
+            Reverse(depth),
+        )
+    });
+    modes.dedup_by_key(|mode| (mode.width, mode.height));
+    modes
+}
+
+/// Returns the first of the modes whose resolution has the smallest distance
+/// to the given one.
+fn closest(modes: &[VidMode], width: u32, height: u32) -> Option<VidMode> {
+    modes.iter().copied().min_by_key(|mode| {
+        let dx = mode.width as i64 - width as i64;
+        let dy = mode.height as i64 - height as i64;
+        dx * dx + dy * dy
+    })
+}
+
+/// Returns the connected monitors, in the same order as the ones given by
+/// [glfw::Glfw::with_connected_monitors].
+fn connected() -> Vec<*mut ffi::GLFWmonitor> {
+    unsafe {
+        let mut count = 0;
+        let monitors = ffi::glfwGetMonitors(&mut count);
+        if monitors.is_null() {
+            return Vec::new();
+        }
+        slice::from_raw_parts(monitors, count as usize).to_vec()
+    }
+}
+
+/// Returns the work area of the monitor as `(x, y, width, height)`.
+fn work_area(monitor: *mut ffi::GLFWmonitor) -> (i32, i32, i32, i32) {
+    let (mut x, mut y, mut width, mut height) = (0, 0, 0, 0);
+    unsafe { ffi::glfwGetMonitorWorkarea(monitor, &mut x, &mut y, &mut width, &mut height) };
+    (x, y, width, height)
+}
+
+/// Returns whether the rectangles as `(x, y, width, height)` overlap.
+fn intersects(a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)) -> bool {
+    a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3
+}
+
+/// Amount of monitor connections and disconnections, which are counted after
+/// any display enables
+/// [repositioning](Display::set_auto_reposition_on_monitor_change).
+static MONITOR_CHANGES: AtomicU64 = AtomicU64::new(0);
+
+extern "C" fn monitor_callback(_: *mut ffi::GLFWmonitor, _: c_int) {
+    MONITOR_CHANGES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Original gamma ramp of a monitor, which is restored when dropped.
+pub(crate) struct GammaRestore {
+    monitor: *mut ffi::GLFWmonitor,
+    original: GammaRamp,
+}
+
+impl GammaRestore {
+    /// Saves the current gamma ramp of the monitor, if the platform has one.
+    fn save(monitor: *mut ffi::GLFWmonitor) -> Option<Self> {
+        unsafe {
+            let ramp = ffi::glfwGetGammaRamp(monitor);
+            if ramp.is_null() {
+                return None;
+            }
+            let ramp = &*ramp;
+            let channel = |channel| slice::from_raw_parts(channel, ramp.size as usize).to_vec();
+            Some(Self {
+                monitor,
+                original: GammaRamp {
+                    red: channel(ramp.red),
+                    green: channel(ramp.green),
+                    blue: channel(ramp.blue),
+                },
+            })
+        }
+    }
+}
+
+impl Drop for GammaRestore {
+    fn drop(&mut self) {
+        if !connected().contains(&self.monitor) {
+            return;
+        }
+        let ramp = ffi::GLFWgammaramp {
+            red: self.original.red.as_mut_ptr(),
+            green: self.original.green.as_mut_ptr(),
+            blue: self.original.blue.as_mut_ptr(),
+            size: self.original.red.len() as _,
+        };
+        unsafe { ffi::glfwSetGammaRamp(self.monitor, &ramp) };
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Returns the monitor the display is on, which is the fullscreen monitor,
+    /// or the one containing the center of the window, or the primary monitor
+    /// if none of them contain it. Null if there are no monitors.
+    fn monitor_ptr(&self) -> *mut ffi::GLFWmonitor {
+        let fullscreen = unsafe { ffi::glfwGetWindowMonitor(self.window.window_ptr()) };
+        if !fullscreen.is_null() {
+            return fullscreen;
+        }
+        let (x, y) = self.window.get_pos();
+        let (width, height) = self.window.get_size();
+        let (x, y) = (x + width / 2, y + height / 2);
+        let containing = connected().into_iter().find(|&monitor| unsafe {
+            let (mut left, mut top) = (0, 0);
+            ffi::glfwGetMonitorPos(monitor, &mut left, &mut top);
+            let vidmode = ffi::glfwGetVideoMode(monitor);
+            !vidmode.is_null()
+                && (left..left + (*vidmode).width).contains(&x)
+                && (top..top + (*vidmode).height).contains(&y)
+        });
+        containing.unwrap_or_else(|| unsafe { ffi::glfwGetPrimaryMonitor() })
+    }
+
+    /// Calls the given function with the [monitor the display is
+    /// on](Self::monitor_ptr).
+    pub(crate) fn with_monitor<R>(&self, f: impl FnOnce(&Monitor) -> R) -> Option<R> {
+        let monitor = self.monitor_ptr();
+        let index = connected().iter().position(|&m| m == monitor)?;
+        self.glfw()
+            .clone()
+            .with_connected_monitors(|_, monitors| monitors.get(index).map(f))
+    }
+
+    /// Switches the window to fullscreen mode on the monitor it is on, with
+    /// the monitor's current video mode and the
+    /// [refresh rate](crate::Options::refresh_rate) of the options, or back
+    /// to windowed mode. Entering saves the position and the size of the
+    /// window, which are restored when leaving; without them, like for a
+    /// window created in fullscreen mode, the window gets the size of the
+    /// [options](crate::Options) at the center of the monitor's work area.
+    /// Use [set_windowed](Self::set_windowed) for leaving to another place.
+    ///
+    /// Moves the same window to the monitor instead of recreating it; thus,
+    /// the OpenGL context and all its objects, like the textures and the
+    /// buffers, survive the toggle. Some drivers can still reset the context
+    /// while the video mode changes, which is reported by
+    /// [context_lost](Self::context_lost).
+    /// Does nothing if there are no monitors, or if the window is already in
+    /// the given mode.
+    pub fn set_fullscreen(&mut self, on: bool) {
+        let window = self.window.window_ptr();
+        let fullscreen = unsafe { !ffi::glfwGetWindowMonitor(window).is_null() };
+        if on == fullscreen {
+            return;
+        }
+        let monitor = self.monitor_ptr();
+        if monitor.is_null() {
+            return;
+        }
+        let vidmode = unsafe { ffi::glfwGetVideoMode(monitor) };
+        if vidmode.is_null() {
+            return;
+        }
+        let (width, height, refresh_rate) =
+            unsafe { ((*vidmode).width, (*vidmode).height, (*vidmode).refreshRate) };
+        if on {
+            let (x, y) = self.window.get_pos();
+            let (window_width, window_height) = self.window.get_size();
+            self.windowed_rect = Some((x, y, window_width, window_height));
+            let refresh_rate = self
+                .options
+                .refresh_rate
+                .map_or(refresh_rate, |rate| rate as i32);
+            unsafe {
+                ffi::glfwSetWindowMonitor(window, monitor, 0, 0, width, height, refresh_rate);
+            }
+        } else {
+            let (x, y, width, height) = self.windowed_rect.take().unwrap_or_else(|| {
+                let (mut x, mut y, mut area_width, mut area_height) = (0, 0, 0, 0);
+                unsafe {
+                    ffi::glfwGetMonitorWorkarea(
+                        monitor,
+                        &mut x,
+                        &mut y,
+                        &mut area_width,
+                        &mut area_height,
+                    );
+                }
+                let (width, height) = (self.options.width, self.options.height);
+                (
+                    center(x, area_width, width),
+                    center(y, area_height, height),
+                    width as i32,
+                    height as i32,
+                )
+            });
+            unsafe {
+                ffi::glfwSetWindowMonitor(
+                    window,
+                    ptr::null_mut(),
+                    x,
+                    y,
+                    width,
+                    height,
+                    ffi::DONT_CARE,
+                );
+            }
+        }
+        self.fit_viewport();
+    }
+
+    /// Sets the gamma of the monitor the display is on, which is 1 normally,
+    /// by generating a gamma ramp from it.
+    /// Consider lowering it for fading to black, or exposing it as a
+    /// brightness setting in exclusive fullscreen games.
+    ///
+    /// **Affects the whole monitor, not just the window!** The original ramp
+    /// is restored when the display is dropped, but not if the process
+    /// crashes. Does nothing on the platforms without gamma ramps, like
+    /// Wayland.
+    pub fn set_gamma(&mut self, gamma: f32) {
+        if let Some(monitor) = self.save_gamma() {
+            unsafe { ffi::glfwSetGamma(monitor, gamma) };
+        }
+    }
+
+    /// Sets the gamma ramp of the monitor the display is on, which maps the
+    /// channel values to the output intensities. The ramps of all the
+    /// channels must have the same size, which is 256 on most platforms.
+    ///
+    /// **Affects the whole monitor, not just the window!** The original ramp
+    /// is restored when the display is dropped, but not if the process
+    /// crashes. Does nothing on the platforms without gamma ramps, like
+    /// Wayland.
+    ///
+    /// # Panics
+    ///
+    /// - If the channels do not have the same size.
+    pub fn set_gamma_ramp(&mut self, red: &[u16], green: &[u16], blue: &[u16]) {
+        assert!(
+            red.len() == green.len() && red.len() == blue.len(),
+            "Gamma ramp channels must have the same size!"
+        );
+        if let Some(monitor) = self.save_gamma() {
+            let ramp = ffi::GLFWgammaramp {
+                red: red.as_ptr() as *mut _,
+                green: green.as_ptr() as *mut _,
+                blue: blue.as_ptr() as *mut _,
+                size: red.len() as _,
+            };
+            unsafe { ffi::glfwSetGammaRamp(monitor, &ramp) };
+        }
+    }
+
+    /// Saves the original gamma ramp of the monitor the display is on, unless
+    /// it is already saved. Restores the one of the previous monitor if the
+    /// display moved. Returns the monitor if it has a gamma ramp.
+    fn save_gamma(&mut self) -> Option<*mut ffi::GLFWmonitor> {
+        let monitor = self.monitor_ptr();
+        if monitor.is_null() {
+            return None;
+        }
+        if self.gamma.as_ref().map(|gamma| gamma.monitor) != Some(monitor) {
+            self.gamma = None;
+            self.gamma = Some(GammaRestore::save(monitor)?);
+        }
+        Some(monitor)
+    }
+
+    /// Returns a snapshot of the monitor the display is on, which is the
+    /// fullscreen monitor, or the one containing the center of the window,
+    /// or the primary monitor if none of them contain it.
+    pub fn monitor_info(&self) -> Option<MonitorInfo> {
+        self.with_monitor(MonitorInfo::new)
+    }
+
+    /// Moves the window to the center of the primary monitor's work area if
+    /// enabled, when a monitor is connected or disconnected and the window
+    /// does not overlap the work area of any monitor anymore.
+    /// Prevents the window from getting lost off screen after unplugging a
+    /// monitor, which is hard to recover from for the users.
+    /// Checked in [update](Self::update), and never in fullscreen mode.
+    /// Installs a GLFW monitor callback, which replaces the one set through
+    /// [glfw::Glfw::set_monitor_callback] if any. Disabled by default.
+    pub fn set_auto_reposition_on_monitor_change(&mut self, on: bool) {
+        self.monitor_changes = on.then(|| MONITOR_CHANGES.load(Ordering::Relaxed));
+        if on {
+            unsafe { ffi::glfwSetMonitorCallback(Some(monitor_callback)) };
+        }
+    }
+
+    /// Repositions the window if enabled and the monitors changed since the
+    /// last check.
+    pub(crate) fn reposition_on_monitor_change(&mut self) {
+        let Some(seen) = self.monitor_changes else {
+            return;
+        };
+        let changes = MONITOR_CHANGES.load(Ordering::Relaxed);
+        if changes == seen {
+            return;
+        }
+        self.monitor_changes = Some(changes);
+        let window = self.window.window_ptr();
+        if unsafe { !ffi::glfwGetWindowMonitor(window).is_null() } {
+            return;
+        }
+        let (x, y) = self.window.get_pos();
+        let (width, height) = self.window.get_size();
+        let rect = (x, y, width, height);
+        if connected()
+            .into_iter()
+            .any(|monitor| intersects(rect, work_area(monitor)))
+        {
+            return;
+        }
+        let primary = unsafe { ffi::glfwGetPrimaryMonitor() };
+        if primary.is_null() {
+            return;
+        }
+        let (left, top, area_width, area_height) = work_area(primary);
+        self.window.set_pos(
+            center(left, area_width, width.max(0) as u32),
+            center(top, area_height, height.max(0) as u32),
+        );
+    }
+
+    /// Returns the [DPI](MonitorInfo::dpi) of the monitor the display is on,
+    /// or [DEFAULT_DPI] if there is no monitor.
+    pub fn monitor_dpi(&self) -> f64 {
+        self.monitor_info()
+            .map_or(DEFAULT_DPI, |monitor| monitor.dpi())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(physical_width: i32) -> MonitorInfo {
+        MonitorInfo {
+            name: None,
+            position: (0, 0),
+            work_area: (0, 0, 1920, 1080),
+            physical_size: (physical_width, 0),
+            video_mode: Some(VidMode {
+                width: 1920,
+                height: 1080,
+                red_bits: 8,
+                green_bits: 8,
+                blue_bits: 8,
+                refresh_rate: 60,
+            }),
+            content_scale: (1.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn dpi_from_physical_size() {
+        assert!((info(508).dpi() - 96.0).abs() < 1e-9);
+        assert_eq!(info(0).dpi(), DEFAULT_DPI);
+    }
+
+    fn mode(width: u32, height: u32, refresh_rate: u32) -> VidMode {
+        VidMode {
+            width,
+            height,
+            red_bits: 8,
+            green_bits: 8,
+            blue_bits: 8,
+            refresh_rate,
+        }
+    }
+
+    fn resolution(mode: &VidMode) -> (u32, u32, u32) {
+        (mode.width, mode.height, mode.refresh_rate)
+    }
+
+    #[test]
+    fn modes_are_distinct_by_resolution() {
+        let modes = distinct_resolutions(vec![
+            mode(1920, 1080, 60),
+            mode(1280, 720, 60),
+            mode(1920, 1080, 144),
+            mode(1280, 1024, 75),
+        ]);
+        let modes: Vec<_> = modes.iter().map(resolution).collect();
+        assert_eq!(
+            modes,
+            [(1280, 720, 60), (1280, 1024, 75), (1920, 1080, 144)]
+        );
+    }
+
+    #[test]
+    fn closest_mode_by_distance() {
+        let modes = [
+            mode(1280, 720, 60),
+            mode(1600, 900, 60),
+            mode(1920, 1080, 60),
+        ];
+        let pick = |width, height| closest(&modes, width, height).map(|m| resolution(&m));
+        assert_eq!(pick(1600, 900), Some((1600, 900, 60)));
+        assert_eq!(pick(1800, 1000), Some((1920, 1080, 60)));
+        assert_eq!(pick(1440, 810), Some((1280, 720, 60)));
+        assert_eq!(pick(640, 480), Some((1280, 720, 60)));
+        assert!(closest(&[], 640, 480).is_none());
+    }
+
+    #[test]
+    fn touching_rectangles_do_not_intersect() {
+        let monitor = (0, 0, 1920, 1080);
+        assert!(intersects((1900, 1000, 800, 600), monitor));
+        assert!(intersects((-100, -100, 4000, 4000), monitor));
+        assert!(!intersects((1920, 0, 800, 600), monitor));
+        assert!(!intersects((0, -600, 800, 600), monitor));
+    }
+}
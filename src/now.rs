@@ -1,10 +1,27 @@
 use glfw::WindowEvent;
 use min_timer::{Now, Sec};
 
-use crate::Display;
+use crate::{
+    time::{FakeTime, TimeSource},
+    Display,
+};
 
 impl<T: FnMut(WindowEvent)> Now for Display<T> {
     fn now(&self) -> Sec {
-        Sec::from(self.glfw().get_time())
+        self.sec()
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Returns the [time](Self::time) as [Sec], which is the same as the
+    /// [Now] implementation without importing the trait.
+    pub fn sec(&self) -> Sec {
+        Sec::from(self.time())
+    }
+}
+
+impl Now for FakeTime {
+    fn now(&self) -> Sec {
+        Sec::from(self.seconds())
     }
 }
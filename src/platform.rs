@@ -0,0 +1,545 @@
+//! Platform specific features that GLFW does not have, done through the
+//! native handles.
+
+use std::os::raw::c_void;
+
+use glfw::WindowEvent;
+
+use crate::{monitor::Rotation, Display};
+
+/// `eglSwapBuffersWithDamageKHR`, or its `EXT` version.
+type SwapBuffersWithDamage = extern "system" fn(*mut c_void, *mut c_void, *const i32, i32) -> u32;
+
+/// EGL display and surface of the window with the swap function that takes
+/// the damage, which is `None` if not supported.
+pub(crate) type DamageSwap = Option<(*mut c_void, *mut c_void, SwapBuffersWithDamage)>;
+
+#[cfg(not(target_os = "macos"))]
+mod egl {
+    use std::os::raw::{c_char, c_void};
+
+    use glfw::ffi::GLFWwindow;
+
+    pub const EXTENSIONS: i32 = 0x3055;
+
+    /// `eglQueryString`.
+    pub type QueryString = extern "system" fn(*mut c_void, i32) -> *const c_char;
+
+    // Native access functions of GLFW.
+    extern "C" {
+        pub fn glfwGetEGLDisplay() -> *mut c_void;
+        pub fn glfwGetEGLSurface(window: *mut GLFWwindow) -> *mut c_void;
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod x11 {
+    use std::os::raw::{c_char, c_int, c_long, c_uint, c_ulong, c_ushort, c_void};
+
+    pub type Atom = c_ulong;
+    pub type Window = c_ulong;
+
+    pub const CLIENT_MESSAGE: c_int = 33;
+    pub const SUBSTRUCTURE_NOTIFY_MASK: c_long = 1 << 19;
+    pub const SUBSTRUCTURE_REDIRECT_MASK: c_long = 1 << 20;
+    pub const NET_WM_STATE_REMOVE: c_long = 0;
+    pub const NET_WM_STATE_ADD: c_long = 1;
+    /// Source indication of normal applications.
+    pub const SOURCE_APPLICATION: c_long = 1;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct XClientMessageEvent {
+        pub type_: c_int,
+        pub serial: c_ulong,
+        pub send_event: c_int,
+        pub display: *mut c_void,
+        pub window: Window,
+        pub message_type: Atom,
+        pub format: c_int,
+        pub data: [c_long; 5],
+    }
+
+    /// Only the used variant of `XEvent`, padded to its full size.
+    #[repr(C)]
+    pub union XEvent {
+        pub client_message: XClientMessageEvent,
+        pub pad: [c_long; 24],
+    }
+
+    // Linked by GLFW.
+    extern "C" {
+        pub fn XInternAtom(
+            display: *mut c_void,
+            name: *const c_char,
+            only_if_exists: c_int,
+        ) -> Atom;
+        pub fn XDefaultRootWindow(display: *mut c_void) -> Window;
+        pub fn XSendEvent(
+            display: *mut c_void,
+            window: Window,
+            propagate: c_int,
+            event_mask: c_long,
+            event: *mut XEvent,
+        ) -> c_int;
+        pub fn XFlush(display: *mut c_void) -> c_int;
+    }
+
+    pub type RrCrtc = c_ulong;
+
+    /// Leading fields of `XRRScreenResources`.
+    #[repr(C)]
+    pub struct XrrScreenResources {
+        pub timestamp: c_ulong,
+        pub config_timestamp: c_ulong,
+    }
+
+    /// Leading fields of `XRRCrtcInfo`.
+    #[repr(C)]
+    pub struct XrrCrtcInfo {
+        pub timestamp: c_ulong,
+        pub x: c_int,
+        pub y: c_int,
+        pub width: c_uint,
+        pub height: c_uint,
+        pub mode: c_ulong,
+        pub rotation: c_ushort,
+    }
+
+    pub const RR_ROTATE_90: c_ushort = 2;
+    pub const RR_ROTATE_180: c_ushort = 4;
+    pub const RR_ROTATE_270: c_ushort = 8;
+
+    pub type GetScreenResourcesCurrent =
+        unsafe extern "C" fn(*mut c_void, Window) -> *mut XrrScreenResources;
+    pub type GetCrtcInfo =
+        unsafe extern "C" fn(*mut c_void, *mut XrrScreenResources, RrCrtc) -> *mut XrrCrtcInfo;
+    pub type Free<T> = unsafe extern "C" fn(*mut T);
+
+    pub const RTLD_LAZY: c_int = 1;
+
+    // Native access functions of GLFW, and the dynamic loader, since GLFW
+    // loads Xrandr at runtime rather than linking it.
+    extern "C" {
+        pub fn glfwGetX11Display() -> *mut c_void;
+        pub fn glfwGetX11Adapter(monitor: *mut glfw::ffi::GLFWmonitor) -> RrCrtc;
+        pub fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+        pub fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        pub fn glfwGetGLXWindow(window: *mut glfw::ffi::GLFWwindow) -> Window;
+    }
+
+    /// `glXJoinSwapGroupNV`.
+    pub type JoinSwapGroup = extern "system" fn(*mut c_void, Window, c_uint) -> c_int;
+    /// `glXBindSwapBarrierNV`.
+    pub type BindSwapBarrier = extern "system" fn(*mut c_void, c_uint, c_uint) -> c_int;
+
+    pub const GLX_SWAP_INTERVAL_EXT: c_int = 0x20F1;
+
+    /// `glXQueryDrawable`.
+    pub type QueryDrawable = extern "system" fn(*mut c_void, Window, c_int, *mut c_uint);
+    /// `glXGetSwapIntervalMESA`.
+    pub type GetSwapIntervalMesa = extern "system" fn() -> c_int;
+}
+
+#[cfg(target_os = "windows")]
+mod win32 {
+    use std::os::raw::{c_char, c_void};
+
+    /// `DEVMODEW` with the display variant of its union.
+    #[repr(C)]
+    pub struct DevModeW {
+        pub device_name: [u16; 32],
+        pub spec_version: u16,
+        pub driver_version: u16,
+        pub size: u16,
+        pub driver_extra: u16,
+        pub fields: u32,
+        pub position: [i32; 2],
+        pub display_orientation: u32,
+        pub display_fixed_output: u32,
+        pub color: i16,
+        pub duplex: i16,
+        pub y_resolution: i16,
+        pub tt_option: i16,
+        pub collate: i16,
+        pub form_name: [u16; 32],
+        pub log_pixels: u16,
+        pub bits_per_pel: u32,
+        pub pels_width: u32,
+        pub pels_height: u32,
+        pub display_flags: u32,
+        pub display_frequency: u32,
+        pub icm_method: u32,
+        pub icm_intent: u32,
+        pub media_type: u32,
+        pub dither_type: u32,
+        pub reserved1: u32,
+        pub reserved2: u32,
+        pub panning_width: u32,
+        pub panning_height: u32,
+    }
+
+    pub const ENUM_CURRENT_SETTINGS: u32 = u32::MAX;
+    pub const DMDO_90: u32 = 1;
+    pub const DMDO_180: u32 = 2;
+    pub const DMDO_270: u32 = 3;
+
+    // Native access function of GLFW.
+    extern "C" {
+        pub fn glfwGetWin32Adapter(monitor: *mut glfw::ffi::GLFWmonitor) -> *const c_char;
+    }
+
+    #[link(name = "user32")]
+    extern "system" {
+        pub fn EnumDisplaySettingsW(device: *const u16, mode: u32, dev_mode: *mut DevModeW) -> i32;
+        pub fn GetDC(window: *mut c_void) -> *mut c_void;
+        pub fn ReleaseDC(window: *mut c_void, dc: *mut c_void) -> i32;
+    }
+
+    /// `wglJoinSwapGroupNV`.
+    pub type JoinSwapGroup = extern "system" fn(*mut c_void, u32) -> i32;
+    /// `wglBindSwapBarrierNV`.
+    pub type BindSwapBarrier = extern "system" fn(u32, u32) -> i32;
+    /// `wglGetSwapIntervalEXT`.
+    pub type GetSwapInterval = extern "system" fn() -> i32;
+}
+
+/// Returns the rotation of the monitor, through RandR on X11 and the display
+/// settings on Windows, or [Rotation::Normal] elsewhere.
+pub(crate) fn monitor_rotation(monitor: *mut glfw::ffi::GLFWmonitor) -> Rotation {
+    #[cfg(target_os = "linux")]
+    unsafe {
+        use std::{ffi::CStr, mem};
+        use x11::*;
+        let display = glfwGetX11Display();
+        let crtc = glfwGetX11Adapter(monitor);
+        let xrandr = dlopen(c"libXrandr.so.2".as_ptr(), RTLD_LAZY);
+        if display.is_null() || crtc == 0 || xrandr.is_null() {
+            return Rotation::Normal;
+        }
+        let symbol = |name: &CStr| dlsym(xrandr, name.as_ptr());
+        let symbols = [
+            symbol(c"XRRGetScreenResourcesCurrent"),
+            symbol(c"XRRGetCrtcInfo"),
+            symbol(c"XRRFreeCrtcInfo"),
+            symbol(c"XRRFreeScreenResources"),
+        ];
+        if symbols.iter().any(|symbol| symbol.is_null()) {
+            return Rotation::Normal;
+        }
+        let get_resources = mem::transmute::<*mut c_void, GetScreenResourcesCurrent>(symbols[0]);
+        let get_crtc_info = mem::transmute::<*mut c_void, GetCrtcInfo>(symbols[1]);
+        let free_crtc_info = mem::transmute::<*mut c_void, Free<XrrCrtcInfo>>(symbols[2]);
+        let free_resources = mem::transmute::<*mut c_void, Free<XrrScreenResources>>(symbols[3]);
+        let resources = get_resources(display, XDefaultRootWindow(display));
+        if resources.is_null() {
+            return Rotation::Normal;
+        }
+        let info = get_crtc_info(display, resources, crtc);
+        let rotation = if info.is_null() {
+            Rotation::Normal
+        } else {
+            let rotation = match (*info).rotation & 0xf {
+                RR_ROTATE_90 => Rotation::Rot90,
+                RR_ROTATE_180 => Rotation::Rot180,
+                RR_ROTATE_270 => Rotation::Rot270,
+                _ => Rotation::Normal,
+            };
+            free_crtc_info(info);
+            rotation
+        };
+        free_resources(resources);
+        rotation
+    }
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use std::ffi::CStr;
+        use win32::*;
+        let adapter = glfwGetWin32Adapter(monitor);
+        if adapter.is_null() {
+            return Rotation::Normal;
+        }
+        let device: Vec<u16> = CStr::from_ptr(adapter)
+            .to_string_lossy()
+            .encode_utf16()
+            .chain([0])
+            .collect();
+        let mut mode: DevModeW = std::mem::zeroed();
+        mode.size = std::mem::size_of::<DevModeW>() as u16;
+        if EnumDisplaySettingsW(device.as_ptr(), ENUM_CURRENT_SETTINGS, &mut mode) == 0 {
+            return Rotation::Normal;
+        }
+        match mode.display_orientation {
+            DMDO_90 => Rotation::Rot90,
+            DMDO_180 => Rotation::Rot180,
+            DMDO_270 => Rotation::Rot270,
+            _ => Rotation::Normal,
+        }
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    {
+        let _ = monitor;
+        Rotation::Normal
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Makes the window visible on all the virtual desktops, or only on its
+    /// own one, by asking the window manager for the `_NET_WM_STATE_STICKY`
+    /// state. Meant for overlays and kiosks.
+    ///
+    /// Only works on X11 with a window manager that follows the EWMH, which
+    /// most do; does nothing on the other platforms.
+    pub fn set_sticky_across_workspaces(&mut self, on: bool) {
+        #[cfg(target_os = "linux")]
+        unsafe {
+            use x11::*;
+            let display = self.glfw().get_x11_display();
+            let window = self.window.get_x11_window() as Window;
+            if display.is_null() || window == 0 {
+                return;
+            }
+            let state = XInternAtom(display, c"_NET_WM_STATE".as_ptr(), 0);
+            let sticky = XInternAtom(display, c"_NET_WM_STATE_STICKY".as_ptr(), 0);
+            let action = if on {
+                NET_WM_STATE_ADD
+            } else {
+                NET_WM_STATE_REMOVE
+            };
+            let mut event = XEvent {
+                client_message: XClientMessageEvent {
+                    type_: CLIENT_MESSAGE,
+                    serial: 0,
+                    send_event: 1,
+                    display,
+                    window,
+                    message_type: state,
+                    format: 32,
+                    data: [action, sticky as _, 0, SOURCE_APPLICATION, 0],
+                },
+            };
+            XSendEvent(
+                display,
+                XDefaultRootWindow(display),
+                0,
+                SUBSTRUCTURE_REDIRECT_MASK | SUBSTRUCTURE_NOTIFY_MASK,
+                &mut event,
+            );
+            XFlush(display);
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = on;
+    }
+
+    /// Adds the window to the given swap group of `NV_swap_group`, whose
+    /// windows swap their buffers together, or removes it from its group with
+    /// zero. Meant for the video walls, where the outputs of many windows,
+    /// even on many computers, must show the same frame.
+    /// Returns whether it succeeded, which is `false` without
+    /// `WGL_NV_swap_group` or `GLX_NV_swap_group`; these are only supported
+    /// by the NVIDIA professional GPUs, like Quadro, and the swaps are only
+    /// synchronized across the GPUs with a G-Sync or Quadro Sync card.
+    /// Always `false` on the other platforms, and on Wayland.
+    /// Uses the current context, which must be the display's.
+    pub fn join_swap_group(&mut self, group: u32) -> bool {
+        let joined = self.join_swap_group_raw(group);
+        if joined {
+            self.swap_group = group;
+        }
+        joined
+    }
+
+    /// Binds the [swap group](Self::join_swap_group) of the window to the
+    /// given barrier of `NV_swap_group`, which synchronizes the swaps of the
+    /// groups on all the computers that bind to it through the sync cards, or
+    /// unbinds it with zero.
+    /// Returns whether it succeeded, which is `false` if the window is not in
+    /// a group, or without the hardware and the extension, like
+    /// [join_swap_group](Self::join_swap_group).
+    pub fn bind_swap_barrier(&mut self, barrier: u32) -> bool {
+        if self.swap_group == 0 {
+            return false;
+        }
+        self.bind_swap_barrier_raw(self.swap_group, barrier)
+    }
+
+    /// Returns the swap interval that the driver actually applies to the
+    /// window, which can differ from the requested one when the driver
+    /// settings override it, like `__GL_SYNC_TO_VBLANK` of NVIDIA or
+    /// `vblank_mode` of Mesa on Linux, or the control panels on Windows.
+    /// Then, turning the vsync off seems to do nothing.
+    ///
+    /// Queried with `GLX_EXT_swap_control` or `GLX_MESA_swap_control` on X11,
+    /// and `WGL_EXT_swap_control` on Windows. `None` without them, with the
+    /// EGL contexts, and on the other platforms, like Wayland and macOS, which
+    /// cannot report it.
+    /// Uses the current context, which must be the display's.
+    pub fn effective_swap_interval(&self) -> Option<i32> {
+        #[cfg(target_os = "windows")]
+        unsafe {
+            let proc = self.glfw().get_proc_address_raw("wglGetSwapIntervalEXT");
+            if !self.glfw().extension_supported("WGL_EXT_swap_control") || proc.is_null() {
+                return None;
+            }
+            let get = std::mem::transmute::<glfw::GLProc, win32::GetSwapInterval>(proc);
+            Some(get())
+        }
+        #[cfg(target_os = "linux")]
+        unsafe {
+            use glfw::{ffi, Context};
+            let window = self.window.window_ptr();
+            let api = ffi::glfwGetWindowAttrib(window, ffi::CONTEXT_CREATION_API);
+            if api != ffi::NATIVE_CONTEXT_API {
+                return None;
+            }
+            let query = self.glfw().get_proc_address_raw("glXQueryDrawable");
+            if self.glfw().extension_supported("GLX_EXT_swap_control") && !query.is_null() {
+                let display = self.glfw().get_x11_display();
+                let drawable = x11::glfwGetGLXWindow(window);
+                if display.is_null() || drawable == 0 {
+                    return None;
+                }
+                let query = std::mem::transmute::<glfw::GLProc, x11::QueryDrawable>(query);
+                let mut interval = 0;
+                query(display, drawable, x11::GLX_SWAP_INTERVAL_EXT, &mut interval);
+                return Some(interval as i32);
+            }
+            let get = self.glfw().get_proc_address_raw("glXGetSwapIntervalMESA");
+            if self.glfw().extension_supported("GLX_MESA_swap_control") && !get.is_null() {
+                let get = std::mem::transmute::<glfw::GLProc, x11::GetSwapIntervalMesa>(get);
+                return Some(get());
+            }
+            None
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        None
+    }
+
+    /// Calls the platform's `JoinSwapGroupNV` if supported.
+    fn join_swap_group_raw(&self, group: u32) -> bool {
+        #[cfg(target_os = "windows")]
+        unsafe {
+            let proc = self.glfw().get_proc_address_raw("wglJoinSwapGroupNV");
+            if !self.glfw().extension_supported("WGL_NV_swap_group") || proc.is_null() {
+                return false;
+            }
+            let join = std::mem::transmute::<glfw::GLProc, win32::JoinSwapGroup>(proc);
+            let hwnd = self.window.get_win32_window();
+            let dc = win32::GetDC(hwnd);
+            if dc.is_null() {
+                return false;
+            }
+            let joined = join(dc, group) != 0;
+            win32::ReleaseDC(hwnd, dc);
+            joined
+        }
+        #[cfg(target_os = "linux")]
+        unsafe {
+            use glfw::Context;
+            let proc = self.glfw().get_proc_address_raw("glXJoinSwapGroupNV");
+            if !self.glfw().extension_supported("GLX_NV_swap_group") || proc.is_null() {
+                return false;
+            }
+            let join = std::mem::transmute::<glfw::GLProc, x11::JoinSwapGroup>(proc);
+            let display = self.glfw().get_x11_display();
+            let drawable = x11::glfwGetGLXWindow(self.window.window_ptr());
+            !display.is_null() && drawable != 0 && join(display, drawable, group) != 0
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            let _ = group;
+            false
+        }
+    }
+
+    /// Calls the platform's `BindSwapBarrierNV` if supported.
+    fn bind_swap_barrier_raw(&self, group: u32, barrier: u32) -> bool {
+        #[cfg(target_os = "windows")]
+        unsafe {
+            let proc = self.glfw().get_proc_address_raw("wglBindSwapBarrierNV");
+            if !self.glfw().extension_supported("WGL_NV_swap_group") || proc.is_null() {
+                return false;
+            }
+            let bind = std::mem::transmute::<glfw::GLProc, win32::BindSwapBarrier>(proc);
+            bind(group, barrier) != 0
+        }
+        #[cfg(target_os = "linux")]
+        unsafe {
+            let proc = self.glfw().get_proc_address_raw("glXBindSwapBarrierNV");
+            if !self.glfw().extension_supported("GLX_NV_swap_group") || proc.is_null() {
+                return false;
+            }
+            let bind = std::mem::transmute::<glfw::GLProc, x11::BindSwapBarrier>(proc);
+            let display = self.glfw().get_x11_display();
+            !display.is_null() && bind(display, group, barrier) != 0
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        {
+            let _ = (group, barrier);
+            false
+        }
+    }
+
+    /// Swaps the buffers with the damaged rectangles as
+    /// `(x, y, width, height)` in framebuffer pixels from the bottom left
+    /// corner. Returns `false` without swapping if the context does not
+    /// support it, which is checked once.
+    pub(crate) fn swap_damaged(&mut self, rects: &[(i32, i32, i32, i32)]) -> bool {
+        if self.damage_swap.is_none() {
+            self.damage_swap = Some(self.load_damage_swap());
+        }
+        let Some(Some((display, surface, swap))) = self.damage_swap else {
+            return false;
+        };
+        let rects: Vec<i32> = rects
+            .iter()
+            .flat_map(|&(x, y, width, height)| [x, y, width, height])
+            .collect();
+        swap(display, surface, rects.as_ptr(), (rects.len() / 4) as i32) != 0
+    }
+
+    /// Loads the swap with damage if the context is created by EGL and it has
+    /// one of the extensions for it.
+    fn load_damage_swap(&self) -> DamageSwap {
+        #[cfg(not(target_os = "macos"))]
+        unsafe {
+            use glfw::{ffi, Context};
+            use std::ffi::CStr;
+            let window = self.window.window_ptr();
+            let api = ffi::glfwGetWindowAttrib(window, ffi::CONTEXT_CREATION_API);
+            if api != ffi::EGL_CONTEXT_API {
+                return None;
+            }
+            let (display, surface) = (egl::glfwGetEGLDisplay(), egl::glfwGetEGLSurface(window));
+            let query = self.glfw().get_proc_address_raw("eglQueryString");
+            if display.is_null() || surface.is_null() || query.is_null() {
+                return None;
+            }
+            let query = std::mem::transmute::<glfw::GLProc, egl::QueryString>(query);
+            let extensions = query(display, egl::EXTENSIONS);
+            if extensions.is_null() {
+                return None;
+            }
+            let extensions = CStr::from_ptr(extensions).to_string_lossy();
+            for (extension, name) in [
+                (
+                    "EGL_KHR_swap_buffers_with_damage",
+                    "eglSwapBuffersWithDamageKHR",
+                ),
+                (
+                    "EGL_EXT_swap_buffers_with_damage",
+                    "eglSwapBuffersWithDamageEXT",
+                ),
+            ] {
+                let proc = self.glfw().get_proc_address_raw(name);
+                if extensions.split_whitespace().any(|e| e == extension) && !proc.is_null() {
+                    let swap = std::mem::transmute::<glfw::GLProc, SwapBuffersWithDamage>(proc);
+                    return Some((display, surface, swap));
+                }
+            }
+            None
+        }
+        #[cfg(target_os = "macos")]
+        None
+    }
+}
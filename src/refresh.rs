@@ -0,0 +1,108 @@
+use std::{any::Any, cell::RefCell, os::raw::c_void, slice};
+
+use glfw::{ffi, Context, WindowEvent};
+
+use crate::Display;
+
+/// Hook of [Display::on_refresh] and the function that calls it.
+/// Boxed as [Any] rather than as a `dyn FnMut(&mut Display<T>)`, which would
+/// make dropping the display use the handler; thus, the borrows of the
+/// handler would have to outlive the display.
+pub(crate) type RefreshHook<T> = (Box<dyn Any>, fn(&mut dyn Any, &mut Display<T>));
+
+/// Calls the hook, which is of the type `H`.
+fn call_hook<T: FnMut(WindowEvent), H: FnMut(&mut Display<T>) + 'static>(
+    hook: &mut dyn Any,
+    display: &mut Display<T>,
+) {
+    if let Some(hook) = hook.downcast_mut::<H>() {
+        hook(display);
+    }
+}
+
+/// Display that is polling on this thread, as its window, a pointer to it and
+/// the function that refreshes it through the pointer.
+type Polling = (*mut ffi::GLFWwindow, *mut c_void, fn(*mut c_void));
+
+thread_local! {
+    static POLLING: RefCell<Vec<Polling>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Refreshes the polling display whose window the event is for.
+extern "C" fn refresh_callback(window: *mut ffi::GLFWwindow) {
+    let polling = POLLING.with(|p| p.borrow().iter().find(|p| p.0 == window).copied());
+    if let Some((_, display, refresh)) = polling {
+        refresh(display);
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Sets the hook that redraws the window when the platform asks for it,
+    /// which is called right after the handler gets the
+    /// [refresh event](glfw::WindowEvent::Refresh).
+    ///
+    /// Refresh events are handled while they are polled, instead of after the
+    /// [pump](Self::pump), since some platforms keep polling until a live
+    /// resize ends. Thus, rendering in the hook keeps the window from going
+    /// black while its edges are dragged, even when the application only
+    /// renders on demand. Refresh events are not sent to the
+    /// [receiver](Self::events) anymore.
+    ///
+    /// The hook must not [update](Self::update) or [pump](Self::pump) the
+    /// display, since GLFW does not allow polling from a callback.
+    pub fn on_refresh<H: FnMut(&mut Display<T>) + 'static>(&mut self, hook: H) {
+        self.refresh = Some((Box::new(hook), call_hook::<T, H>));
+        self.install_refresh_callback();
+    }
+
+    /// Replaces the refresh callback of the window with the one that calls
+    /// the hook while polling.
+    pub(crate) fn install_refresh_callback(&mut self) {
+        unsafe {
+            ffi::glfwSetWindowRefreshCallback(self.window.window_ptr(), Some(refresh_callback));
+        }
+    }
+
+    /// Polls the events with the display set up for refreshing during it.
+    pub(crate) fn poll_events(&mut self) {
+        Self::poll_events_of(slice::from_mut(self));
+    }
+
+    /// Polls the events once for all the displays, with all of them set up
+    /// for refreshing during it.
+    pub(crate) fn poll_events_of(displays: &mut [Self]) {
+        let Some(mut glfw) = displays.first().map(|display| display.glfw().clone()) else {
+            return;
+        };
+        let polling = displays
+            .iter_mut()
+            .map(|display| {
+                (
+                    display.window.window_ptr(),
+                    display as *mut Self as *mut c_void,
+                    Self::refresh_raw as fn(*mut c_void),
+                )
+            })
+            .collect();
+        let previous = POLLING.with(|p| p.replace(polling));
+        glfw.poll_events();
+        POLLING.with(|p| p.replace(previous));
+    }
+
+    fn refresh_raw(display: *mut c_void) {
+        // Only called while the display is polling, which is through an
+        // exclusive reference that is not used until the polling ends.
+        let display = unsafe { &mut *(display as *mut Self) };
+        display.refresh();
+    }
+
+    fn refresh(&mut self) {
+        (self.handler)(WindowEvent::Refresh);
+        if let Some((mut hook, call)) = self.refresh.take() {
+            call(hook.as_mut(), self);
+            if self.refresh.is_none() {
+                self.refresh = Some((hook, call));
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use glfw::WindowEvent;
+
+use crate::{
+    gl::{self, framebuffer::Framebuffer},
+    Display,
+};
+
+/// Filters of the upscaling from the [render scale](Display::set_render_scale)
+/// to the window.
+/// Defaults to [Nearest](Self::Nearest).
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UpscaleFilter {
+    /// Takes the nearest pixel, which keeps the pixel art sharp.
+    #[default]
+    Nearest = gl::NEAREST,
+    /// Interpolates the pixels, which is smoother for the dynamic resolution.
+    Linear = gl::LINEAR,
+}
+
+/// Internal framebuffer with a depth and stencil renderbuffer, which is
+/// deleted with it when dropped.
+struct ScaledTarget {
+    framebuffer: Framebuffer,
+    depth_stencil: u32,
+}
+
+impl ScaledTarget {
+    fn new(width: u32, height: u32) -> Self {
+        let framebuffer = Framebuffer::new(width, height);
+        let mut depth_stencil = 0;
+        gl::GenRenderbuffers(1, &mut depth_stencil);
+        gl::BindRenderbuffer(gl::RENDERBUFFER, depth_stencil);
+        gl::RenderbufferStorage(
+            gl::RENDERBUFFER,
+            gl::DEPTH24_STENCIL8,
+            width as i32,
+            height as i32,
+        );
+        framebuffer.bind();
+        gl::FramebufferRenderbuffer(
+            gl::FRAMEBUFFER,
+            gl::DEPTH_STENCIL_ATTACHMENT,
+            gl::RENDERBUFFER,
+            depth_stencil,
+        );
+        Self {
+            framebuffer,
+            depth_stencil,
+        }
+    }
+}
+
+impl Drop for ScaledTarget {
+    fn drop(&mut self) {
+        gl::DeleteRenderbuffers(1, &self.depth_stencil);
+    }
+}
+
+/// Render scale of a [Display] with its internal framebuffer, which exists
+/// while the scale is not 1.
+pub(crate) struct RenderScale {
+    scale: f32,
+    filter: UpscaleFilter,
+    target: Option<ScaledTarget>,
+}
+
+impl RenderScale {
+    pub(crate) fn new() -> Self {
+        Self {
+            scale: 1.0,
+            filter: UpscaleFilter::Nearest,
+            target: None,
+        }
+    }
+
+    /// Returns whether the frames are drawn to the internal framebuffer.
+    pub(crate) fn is_active(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Drops the internal framebuffer, which is recreated when it is bound.
+    pub(crate) fn drop_target(&mut self) {
+        self.target = None;
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Draws the frames to an internal framebuffer whose size is the window's
+    /// framebuffer size multiplied by the given scale, which is upscaled, or
+    /// downscaled, to the window in every [swap](Self::swap).
+    /// Consider a scale like `0.25` for the pixel art with the
+    /// [Nearest](UpscaleFilter::Nearest) filter, or changing it with the frame
+    /// rate between `0.5` and `1.0` for the dynamic resolution with the
+    /// [Linear](UpscaleFilter::Linear) one. Draws to the window directly with
+    /// `1.0`, which is the default.
+    ///
+    /// The internal framebuffer has RGBA8 color and a 24 bit depth with an 8
+    /// bit stencil, and is resized with the window. It is bound with the
+    /// viewport covering it after this and every swap; thus, draw to the
+    /// [render target](Self::render_target) rather than to the default
+    /// framebuffer after the passes that bind another one. The scissor test
+    /// applies to the upscaling, and should be disabled before the swap.
+    ///
+    /// # Panics
+    ///
+    /// - If the scale is not positive.
+    pub fn set_render_scale(&mut self, scale: f32) {
+        assert!(scale > 0.0, "Render scale must be positive!");
+        self.render_scale.scale = scale;
+        if scale == 1.0 {
+            self.render_scale.drop_target();
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+            self.fit_viewport();
+        } else {
+            self.bind_render_target();
+        }
+    }
+
+    /// Returns the [render scale](Self::set_render_scale).
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale.scale
+    }
+
+    /// Sets the filter of the upscaling of the
+    /// [render scale](Self::set_render_scale).
+    pub fn set_upscale_filter(&mut self, filter: UpscaleFilter) {
+        self.render_scale.filter = filter;
+    }
+
+    /// Returns the framebuffer that the frames are drawn to, which is the
+    /// internal one with a [render scale](Self::set_render_scale), or 0 for
+    /// the window's.
+    pub fn render_target(&self) -> u32 {
+        self.render_scale
+            .target
+            .as_ref()
+            .map_or(0, |target| target.framebuffer.id())
+    }
+
+    /// Recreates the internal framebuffer if the window's framebuffer size
+    /// changed, then binds it and fits the viewport to it. Keeps the old one
+    /// while the window is minimized.
+    pub(crate) fn bind_render_target(&mut self) {
+        let (width, height) = self.window.get_framebuffer_size();
+        if width > 0 && height > 0 {
+            let scale = self.render_scale.scale;
+            let size = |pixels: i32| ((pixels as f32 * scale).round() as u32).max(1);
+            let (width, height) = (size(width), size(height));
+            let current = self.render_scale.target.as_ref().map(|target| {
+                let framebuffer = &target.framebuffer;
+                (framebuffer.width(), framebuffer.height())
+            });
+            if current != Some((width, height)) {
+                self.render_scale.target = Some(ScaledTarget::new(width, height));
+            }
+        }
+        if let Some(target) = &self.render_scale.target {
+            let framebuffer = &target.framebuffer;
+            framebuffer.bind();
+            gl::Viewport(
+                0,
+                0,
+                framebuffer.width() as i32,
+                framebuffer.height() as i32,
+            );
+        }
+    }
+
+    /// Copies the internal framebuffer to the window's with the filter, if
+    /// there is one.
+    pub(crate) fn upscale(&mut self) {
+        let Some(target) = &self.render_scale.target else {
+            return;
+        };
+        let framebuffer = &target.framebuffer;
+        let (width, height) = self.window.get_framebuffer_size();
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, framebuffer.id());
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, 0);
+        gl::BlitFramebuffer(
+            0,
+            0,
+            framebuffer.width() as i32,
+            framebuffer.height() as i32,
+            0,
+            0,
+            width,
+            height,
+            gl::COLOR_BUFFER_BIT,
+            self.render_scale.filter as u32,
+        );
+    }
+}
@@ -0,0 +1,413 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use glfw::WindowEvent;
+
+use crate::{gl, Display};
+
+/// Common `glEnable`/`glDisable` targets.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Depth comparisons and depth buffer updates.
+    DepthTest = gl::DEPTH_TEST,
+    /// Blending of the fragment colors with the framebuffer.
+    Blend = gl::BLEND,
+    /// Culling of the polygons by their winding.
+    CullFace = gl::CULL_FACE,
+    /// Discarding of the fragments outside the scissor box.
+    ScissorTest = gl::SCISSOR_TEST,
+    /// Stencil comparisons and stencil buffer updates.
+    StencilTest = gl::STENCIL_TEST,
+    /// Multisample anti-aliasing, when the framebuffer has samples.
+    Multisample = gl::MULTISAMPLE,
+    /// Conversion of the linear colors to sRGB, when the framebuffer is sRGB.
+    FramebufferSrgb = gl::FRAMEBUFFER_SRGB,
+}
+
+/// Comparisons of the stencil test, between the reference value and the
+/// stencil buffer, both masked.
+/// Defaults to [Always](Self::Always) like OpenGL.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StencilFunc {
+    /// Never passes.
+    Never = gl::NEVER,
+    /// Passes if the reference is less than the stored value.
+    Less = gl::LESS,
+    /// Passes if the reference is less than or equal to the stored value.
+    LessEqual = gl::LEQUAL,
+    /// Passes if the reference is greater than the stored value.
+    Greater = gl::GREATER,
+    /// Passes if the reference is greater than or equal to the stored value.
+    GreaterEqual = gl::GEQUAL,
+    /// Passes if the reference is equal to the stored value.
+    Equal = gl::EQUAL,
+    /// Passes if the reference is not equal to the stored value.
+    NotEqual = gl::NOTEQUAL,
+    /// Always passes.
+    #[default]
+    Always = gl::ALWAYS,
+}
+
+/// Actions on the stored stencil value after the stencil and depth tests.
+/// Defaults to [Keep](Self::Keep) like OpenGL.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StencilOp {
+    /// Keeps the stored value.
+    #[default]
+    Keep = gl::KEEP,
+    /// Sets the stored value to 0.
+    Zero = gl::ZERO,
+    /// Sets the stored value to the reference value.
+    Replace = gl::REPLACE,
+    /// Increments the stored value, clamping it to the maximum.
+    Increment = gl::INCR,
+    /// Increments the stored value, wrapping it to 0 after the maximum.
+    IncrementWrap = gl::INCR_WRAP,
+    /// Decrements the stored value, clamping it to 0.
+    Decrement = gl::DECR,
+    /// Decrements the stored value, wrapping it to the maximum after 0.
+    DecrementWrap = gl::DECR_WRAP,
+    /// Inverts the bits of the stored value.
+    Invert = gl::INVERT,
+}
+
+/// Corner of the window that the clip space's `(-1, -1)` maps to.
+/// Defaults to [LowerLeft](Self::LowerLeft) like OpenGL.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ClipOrigin {
+    /// Bottom left corner, which is the convention of OpenGL.
+    #[default]
+    LowerLeft = gl::LOWER_LEFT,
+    /// Top left corner, which is the convention of Direct3D and Vulkan;
+    /// flips the rendering vertically, and the winding of the polygons.
+    UpperLeft = gl::UPPER_LEFT,
+}
+
+/// Range of the clip space's depth that is mapped to the
+/// [depth range](Display::set_depth_range).
+/// Defaults to [NegativeOneToOne](Self::NegativeOneToOne) like OpenGL.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ClipDepth {
+    /// From -1 to 1, which is the convention of OpenGL. Half of the
+    /// precision of a floating point depth buffer is lost in the mapping.
+    #[default]
+    NegativeOneToOne = gl::NEGATIVE_ONE_TO_ONE,
+    /// From 0 to 1, which is the convention of Direct3D and Vulkan, and
+    /// needed for the reverse-Z.
+    ZeroToOne = gl::ZERO_TO_ONE,
+}
+
+/// Actual format of the window's framebuffer, which can differ from the
+/// requested one, since the drivers choose the closest one they support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FramebufferFormat {
+    /// Amount of samples per pixel, which is 0 without multisampling.
+    pub samples: u32,
+    /// Bits of the red channel of the back color buffer.
+    pub red_bits: u32,
+    /// Bits of the green channel of the back color buffer.
+    pub green_bits: u32,
+    /// Bits of the blue channel of the back color buffer.
+    pub blue_bits: u32,
+    /// Bits of the alpha channel of the back color buffer.
+    pub alpha_bits: u32,
+    /// Bits of the depth buffer, which is 0 without one.
+    pub depth_bits: u32,
+    /// Bits of the stencil buffer, which is 0 without one.
+    pub stencil_bits: u32,
+}
+
+/// Actual settings of the OpenGL context next to the requested ones, for
+/// checking what the driver gave and pasting into bug reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextReport {
+    /// Vendor of the driver, like `"NVIDIA Corporation"`.
+    pub vendor: String,
+    /// Name of the GPU or the renderer, like `"llvmpipe"`.
+    pub renderer: String,
+    /// Full version string of the driver.
+    pub version_string: String,
+    /// Requested version as `(major, minor)`.
+    pub requested_version: (u32, u32),
+    /// Actual version as `(major, minor)`, which can be higher.
+    pub version: (u32, u32),
+    /// Whether the core profile was obtained, which is always requested for
+    /// [ClientApi::OpenGl](crate::ClientApi::OpenGl).
+    pub core_profile: bool,
+    /// Whether the context is forward compatible, which is always requested
+    /// for [ClientApi::OpenGl](crate::ClientApi::OpenGl).
+    pub forward_compatible: bool,
+    /// Requested [debug context](crate::Options::debug_context).
+    pub requested_debug: bool,
+    /// Whether a debug context was obtained.
+    pub debug: bool,
+    /// Requested [no error context](crate::Options::no_error_context).
+    pub requested_no_error: bool,
+    /// Whether a no error context was obtained.
+    pub no_error: bool,
+    /// Requested [samples](crate::Options::msaa).
+    pub requested_samples: Option<u32>,
+    /// Actual samples of the window's framebuffer, which is 0 without
+    /// multisampling.
+    pub samples: u32,
+}
+
+/// Bindings of the context at a moment, for logging them while debugging the
+/// draws that do not show up or the state that leaks between the passes.
+/// Object names are 0 when nothing is bound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GlStateSnapshot {
+    /// Program in use.
+    pub program: u32,
+    /// Bound vertex array object.
+    pub vao: u32,
+    /// Framebuffer bound for drawing, which is 0 for the window's.
+    pub framebuffer: u32,
+    /// Active texture unit, which is 0 for `gl::TEXTURE0`.
+    pub active_texture: u32,
+    /// 2D texture bound to the active texture unit.
+    pub texture: u32,
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Returns the program in use, which is read back from OpenGL.
+    pub fn current_program(&self) -> u32 {
+        gl::get_integer(gl::CURRENT_PROGRAM) as u32
+    }
+
+    /// Returns the bound vertex array object, which is read back from OpenGL.
+    pub fn current_vao(&self) -> u32 {
+        gl::get_integer(gl::VERTEX_ARRAY_BINDING) as u32
+    }
+
+    /// Returns the 2D texture bound to the given texture unit, which is read
+    /// back from OpenGL. The active texture unit is kept.
+    pub fn current_texture(&self, unit: u32) -> u32 {
+        let active = gl::get_integer(gl::ACTIVE_TEXTURE) as u32;
+        gl::ActiveTexture(gl::TEXTURE0 + unit);
+        let texture = gl::get_integer(gl::TEXTURE_BINDING_2D) as u32;
+        gl::ActiveTexture(active);
+        texture
+    }
+
+    /// Returns the framebuffer bound for drawing, which is read back from
+    /// OpenGL.
+    pub fn current_framebuffer(&self) -> u32 {
+        gl::get_integer(gl::DRAW_FRAMEBUFFER_BINDING) as u32
+    }
+
+    /// Reads back the bindings of the context all at once.
+    pub fn gl_state(&self) -> GlStateSnapshot {
+        GlStateSnapshot {
+            program: self.current_program(),
+            vao: self.current_vao(),
+            framebuffer: self.current_framebuffer(),
+            active_texture: (gl::get_integer(gl::ACTIVE_TEXTURE) as u32)
+                .saturating_sub(gl::TEXTURE0),
+            texture: gl::get_integer(gl::TEXTURE_BINDING_2D) as u32,
+        }
+    }
+
+    /// Returns whether the context was reset by the driver since the display
+    /// was created, like after a GPU crash or on some drivers after a
+    /// [fullscreen](Self::set_fullscreen) toggle. All the objects are lost
+    /// then; thus, the display should be
+    /// [reconfigured](Self::reconfigure) and the resources reloaded.
+    /// Stays `true` once a reset is seen.
+    /// Only the contexts with robustness report the resets, which can be
+    /// requested with `WindowHint::ContextRobustness` in the
+    /// [extra hints](crate::Options::extra_hints); always `false` otherwise.
+    pub fn context_lost(&self) -> bool {
+        if !self.context_lost.get() && gl::GetGraphicsResetStatus() != gl::NO_ERROR {
+            self.context_lost.set(true);
+        }
+        self.context_lost.get()
+    }
+
+    /// Reads back the settings of the context into a [ContextReport].
+    pub fn verify_context(&self) -> ContextReport {
+        let string = |name| {
+            let string = gl::GetString(name);
+            if string.is_null() {
+                return String::new();
+            }
+            unsafe { CStr::from_ptr(string as *const c_char) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        let flags = gl::get_integer(gl::CONTEXT_FLAGS) as u32;
+        let profile = gl::get_integer(gl::CONTEXT_PROFILE_MASK) as u32;
+        ContextReport {
+            vendor: string(gl::VENDOR),
+            renderer: string(gl::RENDERER),
+            version_string: string(gl::VERSION),
+            requested_version: self.options.client_api.version(),
+            version: (
+                gl::get_integer(gl::MAJOR_VERSION) as u32,
+                gl::get_integer(gl::MINOR_VERSION) as u32,
+            ),
+            core_profile: profile & gl::CONTEXT_CORE_PROFILE_BIT != 0,
+            forward_compatible: flags & gl::CONTEXT_FLAG_FORWARD_COMPATIBLE_BIT != 0,
+            requested_debug: self.options.debug_context,
+            debug: flags & gl::CONTEXT_FLAG_DEBUG_BIT != 0,
+            requested_no_error: self.options.no_error_context,
+            no_error: flags & gl::CONTEXT_FLAG_NO_ERROR_BIT != 0,
+            requested_samples: self.options.msaa,
+            samples: self.framebuffer_format().samples,
+        }
+    }
+
+    /// Reads back the [FramebufferFormat] of the window, so it can be checked
+    /// against the requested one, like the [msaa](crate::Options::msaa).
+    /// Binds the default framebuffer as the draw one while querying, and
+    /// restores the previous draw binding afterwards. The missing attachments,
+    /// like the depth of a window without it, have 0 bits.
+    pub fn framebuffer_format(&self) -> FramebufferFormat {
+        let previous = gl::get_integer(gl::DRAW_FRAMEBUFFER_BINDING) as u32;
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, 0);
+        let get = |attachment, pname| {
+            let mut value = 0;
+            gl::GetFramebufferAttachmentParameteriv(
+                gl::DRAW_FRAMEBUFFER,
+                attachment,
+                pname,
+                &mut value,
+            );
+            value
+        };
+        let attachment = |attachment, pname| {
+            // Querying the sizes of a missing attachment is an error.
+            let kind = get(attachment, gl::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE) as u32;
+            if kind == gl::NONE {
+                return 0;
+            }
+            get(attachment, pname).max(0) as u32
+        };
+        let format = FramebufferFormat {
+            samples: gl::get_integer(gl::SAMPLES).max(0) as u32,
+            red_bits: attachment(gl::BACK_LEFT, gl::FRAMEBUFFER_ATTACHMENT_RED_SIZE),
+            green_bits: attachment(gl::BACK_LEFT, gl::FRAMEBUFFER_ATTACHMENT_GREEN_SIZE),
+            blue_bits: attachment(gl::BACK_LEFT, gl::FRAMEBUFFER_ATTACHMENT_BLUE_SIZE),
+            alpha_bits: attachment(gl::BACK_LEFT, gl::FRAMEBUFFER_ATTACHMENT_ALPHA_SIZE),
+            depth_bits: attachment(gl::DEPTH, gl::FRAMEBUFFER_ATTACHMENT_DEPTH_SIZE),
+            stencil_bits: attachment(gl::STENCIL, gl::FRAMEBUFFER_ATTACHMENT_STENCIL_SIZE),
+        };
+        gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, previous);
+        format
+    }
+
+    /// Enables or disables the given [Capability].
+    pub fn set_capability(&mut self, cap: Capability, enabled: bool) {
+        if enabled {
+            gl::Enable(cap as u32);
+        } else {
+            gl::Disable(cap as u32);
+        }
+    }
+
+    /// Returns whether the given [Capability] is enabled.
+    pub fn is_enabled(&self, cap: Capability) -> bool {
+        gl::IsEnabled(cap as u32) == gl::TRUE
+    }
+
+    /// Sets the comparison of the stencil test with the reference value,
+    /// where both the reference and the stored value are masked before.
+    /// OpenGL starts with `StencilFunc::Always`, 0 and all ones.
+    /// Needs [Capability::StencilTest] enabled and a stencil buffer.
+    pub fn stencil_func(&mut self, func: StencilFunc, ref_: i32, mask: u32) {
+        gl::StencilFunc(func as u32, ref_, mask);
+    }
+
+    /// Sets the actions when the stencil test fails, when the stencil test
+    /// passes but the depth test fails, and when both pass.
+    /// OpenGL starts with `StencilOp::Keep` for all of them.
+    ///
+    /// For outlines, draw the object with `StencilFunc::Always`, 1 and
+    /// `StencilOp::Replace` on pass; then, draw it scaled up with
+    /// `StencilFunc::NotEqual`, 1 in the outline color.
+    pub fn stencil_op(&mut self, sfail: StencilOp, dpfail: StencilOp, dppass: StencilOp) {
+        gl::StencilOp(sfail as u32, dpfail as u32, dppass as u32);
+    }
+
+    /// Sets the bits of the stencil buffer that can be written, which is all
+    /// ones in the beginning. Consider 0 for keeping the stencil buffer as it
+    /// is while drawing the outlines.
+    pub fn stencil_mask(&mut self, mask: u32) {
+        gl::StencilMask(mask);
+    }
+
+    /// Sets the window depths that the near and the far planes are mapped to,
+    /// which are 0 and 1 in the beginning. Values are clamped to `[0, 1]`.
+    /// Swapping them, with 1 and 0, reverses the depths; then, the depth test
+    /// must be `gl::GREATER`, and the depth buffer must be cleared to 0.
+    /// That alone does not give the precision of the reverse-Z, which needs
+    /// the [clip control](Self::set_clip_control) as well.
+    pub fn set_depth_range(&mut self, near: f64, far: f64) {
+        gl::DepthRange(near, far);
+    }
+
+    /// Sets how the clip space is mapped to the window, with the core
+    /// `glClipControl` of OpenGL 4.5.
+    ///
+    /// The projection matrix must produce the depths that the
+    /// [ClipDepth] expects: the usual OpenGL projections, like
+    /// `gl::math::perspective`, map the near and the far
+    /// planes to -1 and 1, which only fills half of the range with
+    /// [ZeroToOne](ClipDepth::ZeroToOne). For the reverse-Z, use
+    /// `ClipDepth::ZeroToOne` with a projection that maps the near plane to 1
+    /// and the far one to 0, and keep the [depth range](Self::set_depth_range)
+    /// at 0 and 1. The depth test and the clearing are reversed like with the
+    /// reversed depth range; a floating point depth buffer, like
+    /// `gl::DEPTH_COMPONENT32F`, is what gives the far objects the precision.
+    pub fn set_clip_control(&mut self, origin: ClipOrigin, depth: ClipDepth) {
+        gl::ClipControl(origin as u32, depth as u32);
+    }
+
+    /// Enables the primitive restart with the given index, or disables it with
+    /// `None`, which is the default. An element with the restart index ends
+    /// the current strip or fan and starts a new one in the indexed draws,
+    /// like `glDrawElements`; thus, many triangle strips, like the rows of a
+    /// terrain, can be drawn in a single call.
+    ///
+    /// The index is compared to the elements as they are stored; thus, it
+    /// must fit the index type: consider `Some(u16::MAX as u32)` for the
+    /// `gl::UNSIGNED_SHORT` indices, or `Some(u32::MAX)` for the
+    /// `gl::UNSIGNED_INT` ones. Uses `GL_PRIMITIVE_RESTART` with
+    /// `glPrimitiveRestartIndex`, and disables `GL_PRIMITIVE_RESTART_FIXED_INDEX`,
+    /// which would always restart at the maximum of the index type instead,
+    /// and overrides the given index while enabled.
+    pub fn set_primitive_restart(&mut self, index: Option<u32>) {
+        gl::Disable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+        match index {
+            Some(index) => {
+                gl::Enable(gl::PRIMITIVE_RESTART);
+                gl::PrimitiveRestartIndex(index);
+            }
+            None => gl::Disable(gl::PRIMITIVE_RESTART),
+        }
+    }
+
+    /// Enables or disables the filtering across the faces of the cubemaps,
+    /// which is `GL_TEXTURE_CUBE_MAP_SEAMLESS`. Without it, the edges of the
+    /// faces show as seams on the skyboxes and the blurry mip levels of the
+    /// reflections. Disabled in the beginning, as OpenGL requires.
+    pub fn set_seamless_cubemaps(&mut self, on: bool) {
+        if on {
+            gl::Enable(gl::TEXTURE_CUBE_MAP_SEAMLESS);
+        } else {
+            gl::Disable(gl::TEXTURE_CUBE_MAP_SEAMLESS);
+        }
+    }
+
+    /// Returns the highest anisotropic filtering level the textures can have,
+    /// which is at least 16 on most drivers, and 1 if not supported.
+    /// Reads the core `GL_MAX_TEXTURE_MAX_ANISOTROPY` of OpenGL 4.6, which has
+    /// the same value as the older `_EXT` one.
+    pub fn max_anisotropy(&self) -> f32 {
+        gl::get_float(gl::MAX_TEXTURE_MAX_ANISOTROPY).max(1.0)
+    }
+}
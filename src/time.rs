@@ -0,0 +1,90 @@
+use std::{cell::Cell, rc::Rc};
+
+use glfw::{ffi, Glfw};
+
+/// Returns the raw value of the GLFW timer in [ticks](timer_frequency),
+/// which is the integer that the seconds of the timer are computed from.
+/// Meant for sub-millisecond frame pacing, which loses precision with the
+/// `f64` seconds of a long running timer. Only the differences of the values
+/// are meaningful. Zero if GLFW is not initialized.
+pub fn timer_value() -> u64 {
+    unsafe { ffi::glfwGetTimerValue() }
+}
+
+/// Returns the amount of [timer ticks](timer_value) per second, which
+/// depends on the platform, like 10 MHz from `QueryPerformanceFrequency` on
+/// Windows, or 1 GHz from `clock_gettime` on Linux. Zero if GLFW is not
+/// initialized.
+pub fn timer_frequency() -> u64 {
+    unsafe { ffi::glfwGetTimerFrequency() }
+}
+
+/// Source of the raw time in seconds, which the [Display](crate::Display)'s
+/// clock is built on.
+/// Replaceable by [Display::set_time_source](crate::Display::set_time_source),
+/// for example with a [FakeTime] in tests.
+pub trait TimeSource {
+    /// Returns the current time in seconds, which must never decrease.
+    fn seconds(&self) -> f64;
+}
+
+/// GLFW timer, which is the default time source of the displays.
+#[derive(Clone, Debug)]
+pub struct GlfwTime(pub Glfw);
+
+impl TimeSource for GlfwTime {
+    fn seconds(&self) -> f64 {
+        self.0.get_time()
+    }
+}
+
+/// Time source that only moves when told to, for testing time dependent
+/// logic deterministically and without a window.
+/// Clones share the same time; thus, a clone can be given to the display
+/// while the test keeps advancing the original.
+/// Implements [min_timer::Now] with the `min_timer` feature, so it can
+/// replace the display in headless tests of the frame timing.
+#[derive(Clone, Debug, Default)]
+pub struct FakeTime(Rc<Cell<f64>>);
+
+impl FakeTime {
+    /// Creates a fake time at the given seconds.
+    pub fn new(seconds: f64) -> Self {
+        Self(Rc::new(Cell::new(seconds)))
+    }
+
+    /// Moves the time forward by the given seconds.
+    pub fn advance(&self, seconds: f64) {
+        self.0.set(self.0.get() + seconds);
+    }
+
+    /// Sets the time to the given seconds.
+    pub fn set(&self, seconds: f64) {
+        self.0.set(seconds);
+    }
+}
+
+impl TimeSource for FakeTime {
+    fn seconds(&self) -> f64 {
+        self.0.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+
+    #[test]
+    fn fake_time_drives_the_clock() {
+        let time = FakeTime::new(5.0);
+        let source = time.clone();
+        let mut clock = Clock::new(source.seconds());
+        time.advance(1.5);
+        clock.pause(source.seconds());
+        time.advance(10.0);
+        clock.resume(source.seconds());
+        time.set(17.0);
+        assert_eq!(clock.elapsed(source.seconds()), 1.5 + 0.5);
+    }
+}
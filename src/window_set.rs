@@ -0,0 +1,121 @@
+use glfw::{Context, Glfw, WindowEvent};
+
+use crate::{Display, Options};
+
+/// Displays that share a GLFW and are updated and rendered together, which is
+/// the loop of the applications with many windows.
+/// Polls the events once per [update](Self::update_all) for all the windows,
+/// instead of once per display; then every display handles its own window's
+/// events, which GLFW already routes to its [receiver](Display::events).
+/// The displays have the same handler type; consider boxing the handlers as
+/// `Box<dyn FnMut(WindowEvent)>` for different ones.
+pub struct WindowSet<T: FnMut(WindowEvent)> {
+    glfw: Glfw,
+    displays: Vec<Display<T>>,
+}
+
+impl<T: FnMut(WindowEvent)> WindowSet<T> {
+    /// Creates an empty set with the GLFW from [init](crate::init).
+    ///
+    /// # Panics
+    ///
+    /// - Same as [init](crate::init).
+    pub fn new() -> Self {
+        Self::with_glfw(crate::init())
+    }
+
+    /// Creates an empty set with the given GLFW.
+    pub fn with_glfw(glfw: Glfw) -> Self {
+        Self {
+            glfw,
+            displays: Vec::new(),
+        }
+    }
+
+    /// Creates a display in the set, whose context becomes the current one.
+    /// Returns its index, which changes when the displays before it are
+    /// [removed](Self::remove).
+    ///
+    /// # Panics
+    ///
+    /// - Same as [Display::new_with_glfw].
+    pub fn create(&mut self, opt: Options, handler: T) -> usize {
+        let display = Display::new_with_glfw(&mut self.glfw, opt, handler);
+        self.displays.push(display);
+        self.displays.len() - 1
+    }
+
+    /// Removes the display at the given index and returns it.
+    ///
+    /// # Panics
+    ///
+    /// - If the index is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Display<T> {
+        self.displays.remove(index)
+    }
+
+    /// Drops the displays whose windows should close, like after the close
+    /// buttons are clicked.
+    pub fn remove_closed(&mut self) {
+        self.displays
+            .retain(|display| !display.window().should_close());
+    }
+
+    /// Returns the display at the given index.
+    pub fn get(&self, index: usize) -> Option<&Display<T>> {
+        self.displays.get(index)
+    }
+
+    /// Returns the display at the given index.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Display<T>> {
+        self.displays.get_mut(index)
+    }
+
+    /// Returns the displays in the order they were created.
+    pub fn displays(&self) -> &[Display<T>] {
+        &self.displays
+    }
+
+    /// Returns the displays in the order they were created.
+    pub fn displays_mut(&mut self) -> &mut [Display<T>] {
+        &mut self.displays
+    }
+
+    /// Returns the amount of displays.
+    pub fn len(&self) -> usize {
+        self.displays.len()
+    }
+
+    /// Returns whether there are no displays, like after all of them are
+    /// [closed](Self::remove_closed), which is when the loop ends.
+    pub fn is_empty(&self) -> bool {
+        self.displays.is_empty()
+    }
+
+    /// Polls the events once for all the windows, then lets every display
+    /// handle its own like in its [update](Display::update).
+    /// The [refresh hooks](Display::on_refresh) of all the displays are called
+    /// while polling.
+    pub fn update_all(&mut self) {
+        Display::poll_events_of(&mut self.displays);
+        for display in &mut self.displays {
+            display.handle_events();
+        }
+    }
+
+    /// Makes the context of every display current and
+    /// [renders](Display::render) it. The context of the last display stays
+    /// current.
+    pub fn render_all(&mut self) {
+        for display in &mut self.displays {
+            display.window_mut().make_current();
+            display.render();
+        }
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Default for WindowSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
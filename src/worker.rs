@@ -0,0 +1,87 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread::{self, JoinHandle},
+};
+
+use glfw::WindowEvent;
+
+use crate::Display;
+
+/// Draw command that a worker sends to be executed on the main thread.
+pub type DrawCommand = Box<dyn FnOnce() + Send>;
+
+/// Sending end of a worker's [draw commands](DrawCommand), which the
+/// [display](Display::spawn_worker) executes in its [render](Display::render).
+#[derive(Clone)]
+pub struct Commands(Sender<DrawCommand>);
+
+impl Commands {
+    /// Sends the given command, which is called on the main thread with the
+    /// display's context current; thus, it can do OpenGL calls.
+    /// Returns `false` if the display is dropped, which is when the worker
+    /// should stop.
+    pub fn send(&self, command: impl FnOnce() + Send + 'static) -> bool {
+        self.0.send(Box::new(command)).is_ok()
+    }
+}
+
+/// Thread of a [worker](Display::spawn_worker).
+pub struct WorkerHandle(JoinHandle<()>);
+
+impl WorkerHandle {
+    /// Returns whether the worker returned or panicked.
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+
+    /// Waits for the worker to return. Returns the panic of the worker as an
+    /// error if it panicked.
+    pub fn join(self) -> thread::Result<()> {
+        self.0.join()
+    }
+}
+
+impl<T: FnMut(WindowEvent)> Display<T> {
+    /// Runs the given work on a new thread, which sends the
+    /// [draw commands](DrawCommand) through the given [Commands]; then they
+    /// are executed in order on the main thread in the [render](Self::render),
+    /// before the swap. Meant for the heavy application logic, like the
+    /// simulations, which should not hold back the event polling.
+    ///
+    /// GLFW and OpenGL stay on the main thread: the display and its window,
+    /// the event polling, the monitors and the OpenGL calls cannot be used by
+    /// the worker; only by the commands, which run on the main thread.
+    /// The commands should capture the data they need by value, like the
+    /// vertices that are uploaded with them.
+    ///
+    /// The commands of many workers are executed worker by worker, in the
+    /// order the workers were spawned.
+    pub fn spawn_worker<W>(&mut self, work: W) -> WorkerHandle
+    where
+        W: FnOnce(Commands) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        self.workers.push(receiver);
+        WorkerHandle(thread::spawn(move || work(Commands(sender))))
+    }
+
+    /// Executes the [draw commands](DrawCommand) that the
+    /// [workers](Self::spawn_worker) sent until now, which is done by the
+    /// [render](Self::render). Call it before a [swap](Self::swap) when
+    /// rendering without the render. Forgets the workers that stopped after
+    /// their last commands.
+    pub fn run_worker_commands(&mut self) {
+        self.workers.retain(run_commands);
+    }
+}
+
+/// Executes the received commands, and returns whether more can come.
+fn run_commands(receiver: &Receiver<DrawCommand>) -> bool {
+    loop {
+        match receiver.try_recv() {
+            Ok(command) => command(),
+            Err(TryRecvError::Empty) => return true,
+            Err(TryRecvError::Disconnected) => return false,
+        }
+    }
+}